@@ -0,0 +1,137 @@
+//! A stand-in for the Jagex hiscores that serves `index_lite` responses from
+//! fixture files, for developing the lookup flow offline.
+//!
+//! Run it with `cargo run --bin hiscore_standin` and start the app with
+//! `HISCORE_BASE_URL=http://127.0.0.1:8081`.
+//!
+//! `GET /m=<table>/index_lite.ws?player=<name>` serves
+//! `<fixtures>/<table>/<name>.txt`, with names lowercased and spaces replaced
+//! by underscores. Missing fixtures return 404, and a few names simulate
+//! upstream failures on every table:
+//!
+//! - `rate_limited`: 429
+//! - `unavailable`: 503
+//! - `slow`: responds after 15 seconds, past the app's request timeout
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const DEFAULT_ADDR: &str = "127.0.0.1:8081";
+const DEFAULT_FIXTURES: &str = "fixtures/hiscores";
+
+fn main() -> std::io::Result<()> {
+    let addr = std::env::var("HISCORE_STANDIN_ADDR").unwrap_or_else(|_| DEFAULT_ADDR.to_string());
+    let fixtures = PathBuf::from(
+        std::env::var("HISCORE_FIXTURES").unwrap_or_else(|_| DEFAULT_FIXTURES.to_string()),
+    );
+
+    let listener = TcpListener::bind(&addr)?;
+    println!(
+        "Serving hiscore fixtures from {} on http://{addr}",
+        fixtures.display()
+    );
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("Connection failed: {e}");
+                continue;
+            }
+        };
+        let fixtures = fixtures.clone();
+        std::thread::spawn(move || {
+            if let Err(e) = handle(stream, &fixtures) {
+                eprintln!("Request failed: {e}");
+            }
+        });
+    }
+
+    Ok(())
+}
+
+fn handle(mut stream: TcpStream, fixtures: &Path) -> std::io::Result<()> {
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+    let target = request_line.split_whitespace().nth(1).unwrap_or_default();
+    println!("{}", request_line.trim());
+
+    let (status, body) = match parse_target(target) {
+        Some((table, player)) => respond(fixtures, &table, &player),
+        None => (400, "Bad request".to_string()),
+    };
+
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        _ => "Service Unavailable",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+fn respond(fixtures: &Path, table: &str, player: &str) -> (u16, String) {
+    match player {
+        "rate_limited" => return (429, String::new()),
+        "unavailable" => return (503, String::new()),
+        "slow" => std::thread::sleep(Duration::from_secs(15)),
+        _ => {}
+    }
+
+    match std::fs::read_to_string(fixtures.join(table).join(format!("{player}.txt"))) {
+        Ok(body) => (200, body),
+        Err(_) => (404, "Not Found".to_string()),
+    }
+}
+
+// Splits `/m=<table>/index_lite.ws?player=<name>` into the table and a
+// normalised player name
+fn parse_target(target: &str) -> Option<(String, String)> {
+    let (path, query) = target.split_once('?')?;
+    let table = path
+        .strip_prefix("/m=")?
+        .strip_suffix("/index_lite.ws")?
+        .to_string();
+    let player = query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("player="))
+        .map(percent_decode)?
+        .trim()
+        .to_lowercase()
+        .replace([' ', '-'], "_");
+
+    let is_safe =
+        |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    (is_safe(&table) && is_safe(&player)).then_some((table, player))
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'+' => decoded.push(b' '),
+            b'%' if idx + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[idx + 1..idx + 3]).unwrap_or_default();
+                match u8::from_str_radix(hex, 16) {
+                    Ok(byte) => {
+                        decoded.push(byte);
+                        idx += 2;
+                    }
+                    Err(_) => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        idx += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
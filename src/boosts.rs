@@ -0,0 +1,356 @@
+use crate::skill::{get_skill_levels, Skill};
+use osrs::types::player::Player;
+use osrs::types::potions::Potion;
+
+/// Length of a game tick in seconds.
+pub const TICK_SECONDS: f64 = 0.6;
+
+/// Ticks between stat restore/decay steps.
+const DECAY_TICKS: u32 = 100;
+/// Ticks between boost decay steps while Preserve is active.
+const PRESERVE_DECAY_TICKS: u32 = 150;
+/// Ticks between points at which any stat can change, with or without Preserve.
+const WINDOW_TICKS: u32 = 50;
+
+/// Cooldown on the imbued and saturated hearts, in minutes.
+pub const HEART_COOLDOWN_MINUTES: u32 = 7;
+
+/// What boosts are averaged over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecaySpan {
+    /// A single kill, from a fresh dose.
+    Kill,
+    /// A whole trip, re-dosing along the way.
+    Trip,
+}
+
+/// Settings for modelling boosts wearing off over the course of a kill or trip.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoostDecay {
+    /// Whether results should be averaged over the decaying boost.
+    pub enabled: bool,
+    pub span: DecaySpan,
+    /// Re-dose interval in minutes, or `None` to drink once at the start.
+    pub redose_minutes: Option<u32>,
+    /// Preserve slows boost decay to one level every 90 seconds.
+    pub preserve: bool,
+}
+
+impl Default for BoostDecay {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            span: DecaySpan::Trip,
+            redose_minutes: Some(5),
+            preserve: false,
+        }
+    }
+}
+
+impl BoostDecay {
+    fn redose_ticks(&self) -> Option<u32> {
+        self.redose_minutes
+            .filter(|&minutes| minutes > 0)
+            .map(|minutes| (minutes as f64 * 60.0 / TICK_SECONDS).round() as u32)
+    }
+
+    /// Level of a stat `elapsed_ticks` after a dose took it from `base` to `boosted`.
+    ///
+    /// Boosts tick down towards the base level and drains restore towards it,
+    /// one level per decay step.
+    pub fn level_after(&self, base: u32, boosted: u32, elapsed_ticks: u32) -> u32 {
+        if boosted > base {
+            let interval = if self.preserve {
+                PRESERVE_DECAY_TICKS
+            } else {
+                DECAY_TICKS
+            };
+            boosted.saturating_sub(elapsed_ticks / interval).max(base)
+        } else {
+            (boosted + elapsed_ticks / DECAY_TICKS).min(base)
+        }
+    }
+
+    /// Fraction of a fight lasting `secs` spent at each number of ticks since
+    /// the last dose.
+    pub fn dose_windows(&self, secs: f64) -> Vec<(u32, f64)> {
+        let total = ((secs / TICK_SECONDS).round() as u32).max(1);
+        let cycle = self.redose_ticks().unwrap_or(total).clamp(1, total);
+        let full_cycles = total / cycle;
+        let remainder = total % cycle;
+
+        let mut windows = Vec::new();
+        let mut start = 0;
+        while start < cycle {
+            let end = (start + WINDOW_TICKS).min(cycle);
+            let mut ticks = (end - start) * full_cycles;
+            if remainder > start {
+                ticks += remainder.min(end) - start;
+            }
+            if ticks > 0 {
+                windows.push((start, ticks as f64 / total as f64));
+            }
+            start = end;
+        }
+
+        windows
+    }
+
+    /// Time-weighted average level of a stat over a fight lasting `secs`.
+    pub fn average_level(&self, base: u32, boosted: u32, secs: f64) -> f64 {
+        self.dose_windows(secs)
+            .iter()
+            .map(|&(elapsed, weight)| self.level_after(base, boosted, elapsed) as f64 * weight)
+            .sum()
+    }
+}
+
+/// A user-defined boost for effects not covered by the standard potions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CustomBoost {
+    pub skill: Skill,
+    /// Flat number of levels added (negative values drain).
+    pub flat: i32,
+    /// Percentage of the base level added on top of the flat amount.
+    pub percent: i32,
+}
+
+impl CustomBoost {
+    /// Number of levels this boost adds to a stat with the given base level.
+    pub fn amount(&self, base: u32) -> i32 {
+        self.flat + (base as i32 * self.percent).div_euclid(100)
+    }
+
+    pub fn label(&self) -> String {
+        let mut label = format!("{:+}", self.flat);
+        if self.percent != 0 {
+            label.push_str(&format!(" {:+}%", self.percent));
+        }
+        label
+    }
+}
+
+/// A single contribution to a stat's current level.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatModifier {
+    pub source: String,
+    pub amount: i32,
+    /// False for boosts overlapped by a stronger boost to the same stat.
+    pub applied: bool,
+}
+
+fn percent_boost(base: u32, flat: i32, percent: i32) -> i32 {
+    flat + (base as i32 * percent) / 100
+}
+
+/// Stat changes caused by a potion, including drains and Hitpoints costs.
+///
+/// Percentages are taken from base levels, as the calculator assumes each
+/// potion is drunk on fresh stats.
+///
+/// Every potion is matched explicitly, so a new one won't compile until its
+/// effects are listed here.
+pub fn potion_effects(potion: Potion, player: &Player) -> Vec<(Skill, i32)> {
+    let base = |skill: Skill| get_skill_levels(player, skill).map_or(0, |(base, _)| base);
+    let boost =
+        |skill: Skill, flat: i32, percent: i32| (skill, percent_boost(base(skill), flat, percent));
+    let drain =
+        |skill: Skill, flat: i32, percent: i32| (skill, -percent_boost(base(skill), flat, percent));
+    let all_combat = |flat: i32, percent: i32| {
+        [
+            Skill::Attack,
+            Skill::Strength,
+            Skill::Defence,
+            Skill::Ranged,
+            Skill::Magic,
+        ]
+        .into_iter()
+        .map(|skill| boost(skill, flat, percent))
+        .collect::<Vec<_>>()
+    };
+
+    match potion {
+        Potion::Attack => vec![boost(Skill::Attack, 3, 10)],
+        Potion::Strength => vec![boost(Skill::Strength, 3, 10)],
+        Potion::Defence => vec![boost(Skill::Defence, 3, 10)],
+        Potion::Ranging => vec![boost(Skill::Ranged, 4, 10)],
+        Potion::Magic => vec![boost(Skill::Magic, 4, 0)],
+        Potion::SuperAttack => vec![boost(Skill::Attack, 5, 15)],
+        Potion::SuperStrength => vec![boost(Skill::Strength, 5, 15)],
+        Potion::SuperDefence => vec![boost(Skill::Defence, 5, 15)],
+        Potion::SuperRanging => vec![boost(Skill::Ranged, 5, 15)],
+        Potion::SuperMagic => vec![boost(Skill::Magic, 5, 15)],
+        Potion::SuperCombat => vec![
+            boost(Skill::Attack, 5, 15),
+            boost(Skill::Strength, 5, 15),
+            boost(Skill::Defence, 5, 15),
+        ],
+        // Weaker than a super combat below 45 Herblore
+        Potion::Moonlight => {
+            let (flat, percent) = if base(Skill::Herblore) >= 45 {
+                (5, 15)
+            } else {
+                (3, 10)
+            };
+            vec![
+                boost(Skill::Attack, flat, percent),
+                boost(Skill::Strength, flat, percent),
+                boost(Skill::Defence, flat, percent),
+            ]
+        }
+        Potion::RubyHarvest => vec![boost(Skill::Strength, 4, 15)],
+        Potion::SapphireGlacialis => vec![boost(Skill::Defence, 4, 15)],
+        Potion::BlackWarlock => vec![boost(Skill::Magic, 4, 15)],
+        Potion::ImbuedHeart => vec![boost(Skill::Magic, 1, 10)],
+        Potion::SaturatedHeart => vec![boost(Skill::Magic, 4, 10)],
+        Potion::SmellingSalts => all_combat(11, 16),
+        Potion::OverloadPlus => {
+            let mut effects = all_combat(6, 16);
+            effects.push((Skill::Hitpoints, -50));
+            effects
+        }
+        Potion::OverloadMinus => {
+            let mut effects = all_combat(4, 10);
+            effects.push((Skill::Hitpoints, -50));
+            effects
+        }
+        Potion::Overload => {
+            let mut effects = all_combat(5, 15);
+            effects.push((Skill::Hitpoints, -50));
+            effects
+        }
+        Potion::SaradominBrew => vec![
+            boost(Skill::Defence, 2, 20),
+            boost(Skill::Hitpoints, 2, 15),
+            drain(Skill::Attack, 2, 10),
+            drain(Skill::Strength, 2, 10),
+            drain(Skill::Ranged, 2, 10),
+            drain(Skill::Magic, 2, 10),
+        ],
+        Potion::ZamorakBrew => vec![
+            boost(Skill::Attack, 2, 20),
+            boost(Skill::Strength, 2, 12),
+            drain(Skill::Defence, 2, 10),
+            drain(Skill::Hitpoints, 0, 12),
+        ],
+        Potion::AncientBrew => vec![
+            boost(Skill::Magic, 2, 5),
+            drain(Skill::Attack, 2, 10),
+            drain(Skill::Strength, 2, 10),
+            drain(Skill::Defence, 2, 10),
+        ],
+        Potion::ForgottenBrew => vec![
+            boost(Skill::Magic, 3, 8),
+            drain(Skill::Attack, 2, 10),
+            drain(Skill::Strength, 2, 10),
+            drain(Skill::Defence, 2, 10),
+        ],
+        // Seers' strength: 10% of Attack, Defence, Ranged and Magic are drained and
+        // a quarter of the total is added to a flat Strength boost
+        Potion::DragonBattleaxe => {
+            let drained = [Skill::Attack, Skill::Defence, Skill::Ranged, Skill::Magic]
+                .into_iter()
+                .map(|skill| drain(skill, 0, 10))
+                .collect::<Vec<_>>();
+            let total: i32 = drained.iter().map(|(_, amount)| -amount).sum();
+            let mut effects = vec![(Skill::Strength, 10 + total / 4)];
+            effects.extend(drained);
+            effects
+        }
+        Potion::None => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decay(preserve: bool, redose_minutes: Option<u32>) -> BoostDecay {
+        BoostDecay {
+            enabled: true,
+            span: DecaySpan::Kill,
+            redose_minutes,
+            preserve,
+        }
+    }
+
+    #[test]
+    fn boosts_drop_a_level_every_minute() {
+        let decay = decay(false, None);
+        assert_eq!(decay.level_after(99, 118, 99), 118);
+        assert_eq!(decay.level_after(99, 118, 100), 117);
+        assert_eq!(decay.level_after(99, 118, 1900), 99);
+        assert_eq!(decay.level_after(99, 118, 5000), 99);
+    }
+
+    #[test]
+    fn preserve_slows_boost_decay_to_90_seconds() {
+        let decay = decay(true, None);
+        assert_eq!(decay.level_after(99, 118, 149), 118);
+        assert_eq!(decay.level_after(99, 118, 150), 117);
+        assert_eq!(decay.level_after(99, 118, 300), 116);
+    }
+
+    #[test]
+    fn drains_restore_without_preserve() {
+        let decay = decay(true, None);
+        assert_eq!(decay.level_after(99, 90, 300), 93);
+        assert_eq!(decay.level_after(99, 90, 10_000), 99);
+    }
+
+    #[test]
+    fn average_level_over_the_fight() {
+        let decay = decay(false, None);
+        assert_eq!(decay.average_level(99, 118, 60.0), 118.0);
+        assert_eq!(decay.average_level(99, 118, 120.0), 117.5);
+    }
+
+    fn maxed_player() -> Player {
+        let mut player = Player::default();
+        for skill in crate::skill::PLAYER_SKILLS {
+            crate::skill::set_skill_base_level(&mut player, skill, 99);
+        }
+        player
+    }
+
+    fn effect(potion: Potion, skill: Skill) -> Option<i32> {
+        potion_effects(potion, &maxed_player())
+            .into_iter()
+            .find(|(affected, _)| *affected == skill)
+            .map(|(_, amount)| amount)
+    }
+
+    #[test]
+    fn super_combat_at_99() {
+        assert_eq!(effect(Potion::SuperCombat, Skill::Attack), Some(19));
+        assert_eq!(effect(Potion::SuperCombat, Skill::Strength), Some(19));
+        assert_eq!(effect(Potion::SuperCombat, Skill::Defence), Some(19));
+    }
+
+    #[test]
+    fn saradomin_brew_drains_at_99() {
+        assert_eq!(effect(Potion::SaradominBrew, Skill::Defence), Some(21));
+        assert_eq!(effect(Potion::SaradominBrew, Skill::Hitpoints), Some(16));
+        assert_eq!(effect(Potion::SaradominBrew, Skill::Attack), Some(-11));
+        assert_eq!(effect(Potion::SaradominBrew, Skill::Magic), Some(-11));
+    }
+
+    #[test]
+    fn zamorak_brew_at_99() {
+        assert_eq!(effect(Potion::ZamorakBrew, Skill::Attack), Some(21));
+        assert_eq!(effect(Potion::ZamorakBrew, Skill::Strength), Some(13));
+        assert_eq!(effect(Potion::ZamorakBrew, Skill::Defence), Some(-11));
+        assert_eq!(effect(Potion::ZamorakBrew, Skill::Hitpoints), Some(-11));
+    }
+
+    #[test]
+    fn no_potion_has_no_effects() {
+        assert!(potion_effects(Potion::None, &maxed_player()).is_empty());
+    }
+
+    #[test]
+    fn redosing_keeps_the_boost_up() {
+        let decay = decay(false, Some(1));
+        assert_eq!(decay.average_level(99, 118, 600.0), 118.0);
+    }
+}
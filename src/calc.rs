@@ -0,0 +1,613 @@
+use crate::boosts::TICK_SECONDS;
+use crate::dot::{dot_damage, dot_sources, effective_kind, DotKind, DotSource};
+use crate::gear_effects::{check_gear_effects, EffectCheck};
+use crate::monster::SelectedMonster;
+use crate::skill::{get_skill_levels, xp_for_level, Skill};
+use crate::spells::{Element, Spell};
+use osrs::types::equipment::{CombatStance, CombatType, GearSlot};
+use osrs::types::player::Player;
+use osrs::types::prayers::Prayer;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+const EQUIPMENT_JSON_STRING: &str = include_str!("../assets/json/equipment.json");
+
+/// Offensive combat style families.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombatClass {
+    Melee,
+    Ranged,
+    Magic,
+}
+
+impl CombatClass {
+    pub fn of(combat_type: CombatType) -> Self {
+        match combat_type {
+            CombatType::Light | CombatType::Standard | CombatType::Heavy => CombatClass::Ranged,
+            CombatType::Magic => CombatClass::Magic,
+            _ => CombatClass::Melee,
+        }
+    }
+
+    /// Skills that affect accuracy and max hit for this class.
+    pub fn skills(&self) -> (Skill, Skill) {
+        match self {
+            CombatClass::Melee => (Skill::Attack, Skill::Strength),
+            CombatClass::Ranged => (Skill::Ranged, Skill::Ranged),
+            CombatClass::Magic => (Skill::Magic, Skill::Magic),
+        }
+    }
+}
+
+/// Percentage boosts from the active prayers.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PrayerBoosts {
+    pub attack: u32,
+    pub strength: u32,
+    pub ranged_attack: u32,
+    pub ranged_strength: u32,
+    pub magic_attack: u32,
+    pub magic_damage: u32,
+    pub defence: u32,
+}
+
+pub fn prayer_boosts(player: &Player) -> PrayerBoosts {
+    let table = [
+        (Prayer::ClarityOfThought, [5, 0, 0, 0, 0, 0, 0]),
+        (Prayer::ImprovedReflexes, [10, 0, 0, 0, 0, 0, 0]),
+        (Prayer::IncredibleReflexes, [15, 0, 0, 0, 0, 0, 0]),
+        (Prayer::BurstOfStrength, [0, 5, 0, 0, 0, 0, 0]),
+        (Prayer::SuperhumanStrength, [0, 10, 0, 0, 0, 0, 0]),
+        (Prayer::UltimateStrength, [0, 15, 0, 0, 0, 0, 0]),
+        (Prayer::ThickSkin, [0, 0, 0, 0, 0, 0, 5]),
+        (Prayer::RockSkin, [0, 0, 0, 0, 0, 0, 10]),
+        (Prayer::SteelSkin, [0, 0, 0, 0, 0, 0, 15]),
+        (Prayer::Chivalry, [15, 18, 0, 0, 0, 0, 20]),
+        (Prayer::Piety, [20, 23, 0, 0, 0, 0, 25]),
+        (Prayer::SharpEye, [0, 0, 5, 5, 0, 0, 0]),
+        (Prayer::HawkEye, [0, 0, 10, 10, 0, 0, 0]),
+        (Prayer::EagleEye, [0, 0, 15, 15, 0, 0, 0]),
+        (Prayer::Deadeye, [0, 0, 18, 18, 0, 0, 5]),
+        (Prayer::Rigour, [0, 0, 20, 23, 0, 0, 25]),
+        (Prayer::MysticWill, [0, 0, 0, 0, 5, 0, 0]),
+        (Prayer::MysticLore, [0, 0, 0, 0, 10, 1, 0]),
+        (Prayer::MysticMight, [0, 0, 0, 0, 15, 2, 0]),
+        (Prayer::MysticVigour, [0, 0, 0, 0, 18, 3, 5]),
+        (Prayer::Augury, [0, 0, 0, 0, 25, 4, 25]),
+    ];
+
+    // Only one prayer of each kind can be active, so take the strongest
+    let mut boosts = PrayerBoosts::default();
+    for (prayer, [att, str, r_att, r_str, m_att, m_dmg, def]) in table {
+        if player.prayers.contains_prayer(prayer) {
+            boosts.attack = boosts.attack.max(att);
+            boosts.strength = boosts.strength.max(str);
+            boosts.ranged_attack = boosts.ranged_attack.max(r_att);
+            boosts.ranged_strength = boosts.ranged_strength.max(r_str);
+            boosts.magic_attack = boosts.magic_attack.max(m_att);
+            boosts.magic_damage = boosts.magic_damage.max(m_dmg);
+            boosts.defence = boosts.defence.max(def);
+        }
+    }
+    boosts
+}
+
+/// Names of all equipped items.
+pub fn equipped_names(player: &Player) -> Vec<String> {
+    [
+        GearSlot::Head,
+        GearSlot::Cape,
+        GearSlot::Neck,
+        GearSlot::Ammo,
+        GearSlot::Weapon,
+        GearSlot::Body,
+        GearSlot::Shield,
+        GearSlot::Legs,
+        GearSlot::Hands,
+        GearSlot::Feet,
+        GearSlot::Ring,
+    ]
+    .iter()
+    .filter_map(|slot| player.get_slot(slot).map(|item| item.name().to_string()))
+    .collect()
+}
+
+pub fn weapon_name(player: &Player) -> String {
+    player
+        .get_slot(&GearSlot::Weapon)
+        .map(|item| item.name().to_string())
+        .unwrap_or_default()
+}
+
+#[derive(Deserialize)]
+struct WeaponSpeed {
+    name: String,
+    slot: String,
+    speed: Option<i32>,
+}
+
+// Attack speed in ticks of each weapon in equipment.json, by name
+static WEAPON_SPEEDS: LazyLock<HashMap<String, u32>> = LazyLock::new(|| {
+    let items: Vec<WeaponSpeed> = serde_json::from_str(EQUIPMENT_JSON_STRING).unwrap_or_default();
+    let mut speeds = HashMap::new();
+    for item in items
+        .into_iter()
+        .filter(|item| item.slot.eq_ignore_ascii_case("weapon"))
+    {
+        // Weapons without a fixed speed are listed as -1
+        if let Some(speed) = item.speed.filter(|&speed| speed > 0) {
+            speeds.entry(item.name).or_insert(speed as u32);
+        }
+    }
+    speeds
+});
+
+/// Ticks between casts of a spell from anything but a powered staff.
+const SPELLCASTING_TICKS: u32 = 5;
+/// Multiplier on equipment magic bonuses while wielding Tumeken's shadow.
+/// Inside the Tombs of Amascut it is 4, which isn't modelled.
+const SHADOW_MULTIPLIER: i32 = 3;
+
+/// Attack speed of the equipped weapon in ticks, including the rapid stance.
+/// Spells cast from a weapon take 5 ticks whatever the weapon's own speed.
+pub fn attack_speed(player: &Player, spell: Option<Spell>) -> u32 {
+    if spell.is_some() {
+        return SPELLCASTING_TICKS;
+    }
+    let weapon = weapon_name(player);
+    let speed = WEAPON_SPEEDS.get(&weapon).copied().unwrap_or(4);
+    let is_ranged = CombatClass::of(player.combat_type()) == CombatClass::Ranged;
+    if is_ranged && matches!(player.combat_stance(), CombatStance::Rapid) {
+        speed.saturating_sub(1).max(1)
+    } else {
+        speed
+    }
+}
+
+/// Max hit of a powered staff from the wielder's magic level, before damage bonuses.
+fn powered_staff_base(weapon: &str, magic_level: u32) -> Option<u32> {
+    let third = magic_level / 3;
+    let base = match weapon {
+        "Trident of the seas" | "Trident of the seas (e)" => third.checked_sub(5)?,
+        "Trident of the swamp" | "Trident of the swamp (e)" => third.checked_sub(2)?,
+        "Sanguinesti staff" | "Holy sanguinesti staff" => third.checked_sub(1)?,
+        "Tumeken's shadow" => third + 1,
+        "Thammaron's sceptre" => third.checked_sub(8)?,
+        "Accursed sceptre" => third.checked_sub(6)?,
+        "Warped sceptre" => (8 * magic_level + 96) / 37,
+        _ => return None,
+    };
+    Some(base)
+}
+
+/// Current levels fed into the offensive formulas, so that hypothetical levels
+/// can be evaluated without touching the player.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OffensiveLevels {
+    pub attack: u32,
+    pub strength: u32,
+    pub ranged: u32,
+    pub magic: u32,
+}
+
+impl OffensiveLevels {
+    pub fn current(player: &Player) -> Self {
+        Self {
+            attack: player.stats.attack.current,
+            strength: player.stats.strength.current,
+            ranged: player.stats.ranged.current,
+            magic: player.stats.magic.current,
+        }
+    }
+
+    pub fn get(&self, skill: Skill) -> u32 {
+        match skill {
+            Skill::Attack => self.attack,
+            Skill::Strength => self.strength,
+            Skill::Ranged => self.ranged,
+            Skill::Magic => self.magic,
+            _ => 0,
+        }
+    }
+
+    pub fn with(mut self, skill: Skill, level: u32) -> Self {
+        match skill {
+            Skill::Attack => self.attack = level,
+            Skill::Strength => self.strength = level,
+            Skill::Ranged => self.ranged = level,
+            Skill::Magic => self.magic = level,
+            _ => {}
+        }
+        self
+    }
+}
+
+/// Accuracy roll and max hit of the player's active style.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OffensiveStats {
+    pub class: CombatClass,
+    pub combat_type: CombatType,
+    pub attack_roll: u32,
+    /// Max hit, or `None` for magic without a powered staff or spell.
+    pub max_hit: Option<u32>,
+    /// The spell the max hit comes from, when casting without a powered staff.
+    pub spell: Option<Spell>,
+}
+
+/// Offensive stats of the active style. `spell` is cast when using magic
+/// without a powered staff.
+pub fn offensive_stats(
+    player: &Player,
+    levels: &OffensiveLevels,
+    spell: Option<Spell>,
+) -> OffensiveStats {
+    let combat_type = player.combat_type();
+    let stance = player.combat_stance();
+    let class = CombatClass::of(combat_type);
+    let prayers = prayer_boosts(player);
+    let bonuses = &player.bonuses;
+
+    let scale = |level: u32, percent: u32| level * (100 + percent) / 100;
+
+    match class {
+        CombatClass::Melee => {
+            let (att_stance, str_stance) = match stance {
+                CombatStance::Accurate => (3, 0),
+                CombatStance::Aggressive => (0, 3),
+                CombatStance::Controlled => (1, 1),
+                _ => (0, 0),
+            };
+            let attack_bonus = match combat_type {
+                CombatType::Stab => bonuses.attack.stab,
+                CombatType::Slash => bonuses.attack.slash,
+                _ => bonuses.attack.crush,
+            };
+            let eff_att = scale(levels.attack, prayers.attack) + att_stance + 8;
+            let eff_str = scale(levels.strength, prayers.strength) + str_stance + 8;
+            OffensiveStats {
+                class,
+                combat_type,
+                attack_roll: eff_att * (attack_bonus + 64).max(0) as u32,
+                max_hit: Some((eff_str * (bonuses.strength.melee + 64).max(0) as u32 + 320) / 640),
+                spell: None,
+            }
+        }
+        CombatClass::Ranged => {
+            let stance_bonus = if matches!(stance, CombatStance::Accurate) {
+                3
+            } else {
+                0
+            };
+            let eff_att = scale(levels.ranged, prayers.ranged_attack) + stance_bonus + 8;
+            let eff_str = scale(levels.ranged, prayers.ranged_strength) + stance_bonus + 8;
+            OffensiveStats {
+                class,
+                combat_type,
+                attack_roll: eff_att * (bonuses.attack.ranged + 64).max(0) as u32,
+                max_hit: Some((eff_str * (bonuses.strength.ranged + 64).max(0) as u32 + 320) / 640),
+                spell: None,
+            }
+        }
+        CombatClass::Magic => {
+            let stance_bonus = if matches!(stance, CombatStance::Accurate) {
+                2
+            } else {
+                0
+            };
+            let eff_att = scale(levels.magic, prayers.magic_attack) + stance_bonus + 9;
+            let weapon = weapon_name(player);
+            // Tumeken's shadow multiplies the equipment bonuses, up to +100% damage
+            let (attack_bonus, equipment_damage) = if weapon == "Tumeken's shadow" {
+                (
+                    bonuses.attack.magic * SHADOW_MULTIPLIER,
+                    (bonuses.strength.magic as f64 * SHADOW_MULTIPLIER as f64).min(100.0),
+                )
+            } else {
+                (bonuses.attack.magic, bonuses.strength.magic as f64)
+            };
+            let damage_percent = equipment_damage + prayers.magic_damage as f64;
+            let staff_base = powered_staff_base(&weapon, levels.magic);
+            let spell = spell.filter(|_| staff_base.is_none());
+            let max_hit = staff_base
+                .or(spell.map(|spell| spell.base_max_hit))
+                .map(|base| (base as f64 * (1.0 + damage_percent / 100.0)).floor() as u32);
+            OffensiveStats {
+                class,
+                combat_type,
+                attack_roll: eff_att * (attack_bonus + 64).max(0) as u32,
+                max_hit,
+                spell,
+            }
+        }
+    }
+}
+
+/// Standard OSRS hit chance from an attack and defence roll.
+pub fn hit_chance(attack_roll: u32, defence_roll: u32) -> f64 {
+    let (att, def) = (attack_roll as f64, defence_roll as f64);
+    if att > def {
+        1.0 - (def + 2.0) / (2.0 * (att + 1.0))
+    } else {
+        att / (2.0 * (def + 1.0))
+    }
+}
+
+/// Expected damage of a successful hit, with each non-zero hit reduced by the
+/// target's flat armour to a minimum of 1.
+pub fn average_hit(max_hit: u32, flat_armour: i32) -> f64 {
+    let armour = flat_armour.max(0) as u32;
+    if armour == 0 {
+        return max_hit as f64 / 2.0;
+    }
+    let total: u32 = (1..=max_hit)
+        .map(|hit| hit.saturating_sub(armour).max(1))
+        .sum();
+    total as f64 / (max_hit + 1) as f64
+}
+
+/// The player's output against the selected monster.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FightStats {
+    /// Accuracy roll and max hit after gear effects against the target.
+    pub offence: OffensiveStats,
+    pub gear_effects: Vec<EffectCheck>,
+    /// Whether the spell being cast matches the target's elemental weakness.
+    pub on_element: bool,
+    pub defence_roll: u32,
+    pub hit_chance: f64,
+    /// Attack speed in ticks.
+    pub attack_speed: u32,
+    /// Damage per second, or `None` without a max hit.
+    pub dps: Option<f64>,
+    /// Damage over time from the loadout.
+    pub dots: Vec<DotResult>,
+    /// Seconds to kill from the monster's current hitpoints, including damage
+    /// over time.
+    pub ttk_secs: Option<f64>,
+}
+
+/// Poison, venom or burn from an equipped item over the fight.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DotResult {
+    pub source: DotSource,
+    /// What the target actually suffers, or `None` when it is immune.
+    pub kind: Option<DotKind>,
+    pub damage: f64,
+}
+
+pub fn fight_stats(player: &Player, monster: &SelectedMonster, spell: Option<Spell>) -> FightStats {
+    fight_stats_at(player, &OffensiveLevels::current(player), monster, spell)
+}
+
+/// The player's output against the selected monster with the given levels in
+/// place of their current ones.
+pub fn fight_stats_at(
+    player: &Player,
+    levels: &OffensiveLevels,
+    monster: &SelectedMonster,
+    spell: Option<Spell>,
+) -> FightStats {
+    let mut offence = offensive_stats(player, levels, spell);
+    let gear_effects = check_gear_effects(player, &monster.edited, offence.class);
+    for check in &gear_effects {
+        match check {
+            EffectCheck::Applies { effect, .. } => {
+                let (acc_num, acc_den) = effect.accuracy;
+                let (dmg_num, dmg_den) = effect.damage;
+                offence.attack_roll = offence.attack_roll * acc_num / acc_den;
+                offence.max_hit = offence.max_hit.map(|hit| hit * dmg_num / dmg_den);
+            }
+            EffectCheck::CannotHarm { .. } => offence.max_hit = offence.max_hit.map(|_| 0),
+            _ => {}
+        }
+    }
+
+    // Spells of the weak element gain the severity as a percentage of both
+    // accuracy and damage
+    let weakness = monster.edited.info.weakness.as_ref().and_then(|weakness| {
+        Some((
+            Element::from_json(&weakness.element)?,
+            weakness.severity.max(0) as u32,
+        ))
+    });
+    let on_element = match (offence.spell.and_then(|spell| spell.element), weakness) {
+        (Some(element), Some((weak_to, severity))) if element == weak_to => {
+            offence.attack_roll += offence.attack_roll * severity / 100;
+            offence.max_hit = offence.max_hit.map(|hit| hit + hit * severity / 100);
+            true
+        }
+        _ => false,
+    };
+    let defence_roll = monster.edited.defence_roll(offence.combat_type);
+    let hit_chance = hit_chance(offence.attack_roll, defence_roll);
+    let attack_speed = attack_speed(player, offence.spell);
+    let dps = offence.max_hit.map(|max_hit| {
+        let per_attack = hit_chance * average_hit(max_hit, monster.edited.bonuses.flat_armour);
+        per_attack / (attack_speed as f64 * TICK_SECONDS)
+    });
+
+    // Each effect's chance per second of being applied
+    let attack_secs = attack_speed as f64 * TICK_SECONDS;
+    let effects: Vec<(DotSource, Option<DotKind>, f64)> = dot_sources(player, offence.class)
+        .into_iter()
+        .map(|source| {
+            let kind = effective_kind(source.kind, &monster.edited.immunities);
+            let proc_rate = source.chance * hit_chance / attack_secs;
+            (source, kind, proc_rate)
+        })
+        .collect();
+    let damage_by = |secs: f64| {
+        let dot: f64 = effects
+            .iter()
+            .filter_map(|(_, kind, rate)| Some(dot_damage((*kind)?, *rate, secs)))
+            .sum();
+        dps.unwrap_or_default() * secs + dot
+    };
+
+    let hp = monster.current_hp as f64;
+    let ttk_secs = match dps.filter(|&dps| dps > 0.0) {
+        None => None,
+        Some(direct) if effects.iter().all(|(_, kind, _)| kind.is_none()) => Some(hp / direct),
+        // Damage over time isn't linear, so search for the kill time
+        Some(direct) => {
+            let (mut low, mut high) = (0.0, hp / direct);
+            for _ in 0..40 {
+                let mid = (low + high) / 2.0;
+                if damage_by(mid) >= hp {
+                    high = mid;
+                } else {
+                    low = mid;
+                }
+            }
+            Some(high)
+        }
+    };
+
+    let fight_secs = ttk_secs.unwrap_or_default();
+    let dots = effects
+        .into_iter()
+        .map(|(source, kind, rate)| DotResult {
+            damage: kind.map_or(0.0, |kind| dot_damage(kind, rate, fight_secs)),
+            source,
+            kind,
+        })
+        .collect();
+
+    FightStats {
+        offence,
+        gear_effects,
+        on_element,
+        defence_roll,
+        hit_chance,
+        attack_speed,
+        dps,
+        dots,
+        ttk_secs,
+    }
+}
+
+/// The next level at which training a skill changes the player's output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Breakpoint {
+    pub skill: Skill,
+    pub level: u32,
+    pub xp_needed: u64,
+    pub description: String,
+}
+
+/// Next max hit or accuracy breakpoint for each skill used by the active style.
+///
+/// Boosts are assumed to stay the same size as the base level rises. Without a
+/// target, every Attack or Magic level is an accuracy breakpoint; with one, a
+/// breakpoint needs at least one percentage point of extra hit chance.
+pub fn next_breakpoints(
+    player: &Player,
+    spell: Option<Spell>,
+    xp: impl Fn(Skill) -> u64,
+    target_defence_roll: Option<u32>,
+) -> Vec<Breakpoint> {
+    let levels = OffensiveLevels::current(player);
+    let current = offensive_stats(player, &levels, spell);
+    let (accuracy_skill, damage_skill) = current.class.skills();
+
+    let mut breakpoints = Vec::new();
+    let mut push = |skill: Skill, level: u32, description: String| {
+        breakpoints.push(Breakpoint {
+            skill,
+            level,
+            xp_needed: xp_for_level(level).saturating_sub(xp(skill)),
+            description,
+        });
+    };
+
+    let mut skills = vec![damage_skill];
+    if accuracy_skill != damage_skill {
+        skills.push(accuracy_skill);
+    }
+
+    for skill in skills {
+        let base = get_skill_levels(player, skill).map_or(1, |(base, _)| base);
+        let boost = levels.get(skill) as i64 - base as i64;
+        let raised = |level: u32| {
+            let boosted = (level as i64 + boost).max(0) as u32;
+            offensive_stats(player, &levels.with(skill, boosted), spell)
+        };
+
+        if let Some(max_hit) = current.max_hit {
+            if let Some(level) = (base + 1..=99)
+                .find(|&level| raised(level).max_hit.is_some_and(|hit| hit > max_hit))
+            {
+                let new_hit = raised(level).max_hit.unwrap_or(max_hit);
+                push(skill, level, format!("Max hit {max_hit} → {new_hit}"));
+                continue;
+            }
+        }
+
+        if skill != accuracy_skill || base >= 99 {
+            continue;
+        }
+        match target_defence_roll {
+            Some(defence_roll) => {
+                let chance = hit_chance(current.attack_roll, defence_roll);
+                if let Some(level) = (base + 1..=99).find(|&level| {
+                    hit_chance(raised(level).attack_roll, defence_roll) >= chance + 0.01
+                }) {
+                    let new_chance = hit_chance(raised(level).attack_roll, defence_roll);
+                    push(
+                        skill,
+                        level,
+                        format!(
+                            "Accuracy {:.1}% → {:.1}%",
+                            chance * 100.0,
+                            new_chance * 100.0
+                        ),
+                    );
+                }
+            }
+            None => {
+                let new_roll = raised(base + 1).attack_roll;
+                push(
+                    skill,
+                    base + 1,
+                    format!("Attack roll {} → {new_roll}", current.attack_roll),
+                );
+            }
+        }
+    }
+
+    breakpoints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn powered_staff_max_hits_at_99_magic() {
+        assert_eq!(powered_staff_base("Trident of the seas", 99), Some(28));
+        assert_eq!(powered_staff_base("Trident of the swamp", 99), Some(31));
+        assert_eq!(powered_staff_base("Sanguinesti staff", 99), Some(32));
+        assert_eq!(powered_staff_base("Tumeken's shadow", 99), Some(34));
+        assert_eq!(powered_staff_base("Thammaron's sceptre", 99), Some(25));
+        assert_eq!(powered_staff_base("Accursed sceptre", 99), Some(27));
+        assert_eq!(powered_staff_base("Kodai wand", 99), None);
+    }
+
+    #[test]
+    fn hit_chance_matches_the_accuracy_formula() {
+        assert_eq!(hit_chance(0, 100), 0.0);
+        assert!((hit_chance(100, 100) - 100.0 / 202.0).abs() < 1e-9);
+        assert!((hit_chance(300, 100) - (1.0 - 102.0 / 602.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn flat_armour_floors_hits_at_one() {
+        assert_eq!(average_hit(10, 0), 5.0);
+        // Hits of 1 to 4 become 1, 5 to 10 lose 3, and misses stay at 0
+        assert_eq!(
+            average_hit(10, 3),
+            (4 + 2 + 3 + 4 + 5 + 6 + 7) as f64 / 11.0
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use crate::hiscores::ActivityKind;
+use crate::skill::format_xp;
+use crate::state::AppState;
+use crate::unlocks::{inferred_unlocks, Unlock};
+use dioxus::prelude::*;
+use strum::IntoEnumIterator;
+
+const ACTIVITY_GROUPS: [(ActivityKind, &str); 3] = [
+    (ActivityKind::Boss, "Bosses"),
+    (ActivityKind::Clue, "Clue scrolls"),
+    (ActivityKind::Activity, "Activities"),
+];
+
+#[component]
+pub fn ActivityScores() -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+    let mut is_collapsed = use_signal(|| true);
+
+    let activities = app_state.read().activities.clone();
+    let unlocked: Vec<(Unlock, bool)> = {
+        let state = app_state.read();
+        Unlock::iter()
+            .map(|unlock| (unlock, state.is_unlocked(unlock)))
+            .collect()
+    };
+    let inferred = inferred_unlocks(&activities);
+
+    rsx! {
+        div { class: "mt-3 text-sm",
+            div {
+                class: "flex items-center justify-between cursor-pointer p-1 hover:bg-gray-800 rounded transition-colors",
+                onclick: move |_| is_collapsed.set(!is_collapsed()),
+                h4 { class: "text-xs font-semibold text-muted", "Kill counts & unlocks" }
+                div {
+                    class: "text-xs text-gray-400 transform transition-transform",
+                    class: if is_collapsed() { "" } else { "rotate-180" },
+                    "▼"
+                }
+            }
+
+            if !is_collapsed() {
+                div { class: "flex flex-col gap-2 mt-1",
+                    div { class: "flex flex-wrap gap-x-3 gap-y-1",
+                        for (unlock, is_unlocked) in unlocked {
+                            label {
+                                class: "flex items-center gap-1 cursor-pointer",
+                                title: if inferred.contains(&unlock) { "The hiscore kill counts suggest this is unlocked" } else { "" },
+                                input {
+                                    "type": "checkbox",
+                                    checked: is_unlocked,
+                                    onchange: move |evt| app_state.write().set_unlocked(unlock, evt.checked()),
+                                }
+                                span { "{unlock.name()}" }
+                                if inferred.contains(&unlock) {
+                                    span { class: "text-xs text-success", "KC" }
+                                }
+                            }
+                        }
+                    }
+
+                    if activities.is_empty() {
+                        div { class: "text-xs text-subtle",
+                            "Look up an account to see its kill counts."
+                        }
+                    }
+                    for (kind, title) in ACTIVITY_GROUPS {
+                        if activities.iter().any(|entry| entry.kind == kind) {
+                            div {
+                                h5 { class: "text-xs text-subtle mb-1", "{title}" }
+                                div { class: "grid grid-cols-2 gap-x-3",
+                                    for entry in activities.iter().filter(|entry| entry.kind == kind) {
+                                        div { class: "flex justify-between gap-2",
+                                            span { class: "truncate", title: "Rank {format_xp(entry.rank)}", "{entry.name}" }
+                                            span { class: "font-bold", "{format_xp(entry.score)}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
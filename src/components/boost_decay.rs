@@ -0,0 +1,126 @@
+use crate::boosts::{DecaySpan, HEART_COOLDOWN_MINUTES};
+use crate::skill::COMBAT_SKILLS;
+use crate::state::AppState;
+use dioxus::prelude::*;
+
+#[component]
+pub fn BoostDecayPanel(has_heart: bool) -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+    let decay = app_state.read().boost_decay;
+    // Seconds of the kill or trip against the target that boosts average over
+    let span_secs = {
+        let state = app_state.read();
+        state
+            .target()
+            .and_then(|monster| state.fight_stats_with_decay(&monster).1)
+    };
+
+    // Only list stats that currently differ from their base level
+    let boosted_skills: Vec<_> = {
+        let state = app_state.read();
+        COMBAT_SKILLS
+            .iter()
+            .filter_map(|&skill| {
+                let (base, current) = state.skill_levels(skill);
+                (base != current).then_some((skill, base, current))
+            })
+            .collect()
+    };
+
+    let redose_value = decay.redose_minutes.unwrap_or(0);
+    let heart_too_often = has_heart
+        && decay
+            .redose_minutes
+            .is_some_and(|minutes| minutes > 0 && minutes < HEART_COOLDOWN_MINUTES);
+
+    rsx! {
+        div { class: "mt-4 flex flex-col gap-2 text-sm",
+            label { class: "flex items-center gap-2 cursor-pointer",
+                input {
+                    "type": "checkbox",
+                    checked: decay.enabled,
+                    onchange: move |evt| app_state.write().boost_decay.enabled = evt.checked(),
+                }
+                span { "Average boosts over the fight" }
+            }
+
+            if decay.enabled {
+                div { class: "flex items-center justify-between gap-2",
+                    span { class: "text-muted", "Average over" }
+                    select {
+                        class: "input h-7 py-0 px-2 text-sm",
+                        onchange: move |evt| {
+                            app_state.write().boost_decay.span = if evt.value() == "kill" {
+                                DecaySpan::Kill
+                            } else {
+                                DecaySpan::Trip
+                            };
+                        },
+                        option { value: "kill", selected: decay.span == DecaySpan::Kill, "Each kill" }
+                        option { value: "trip", selected: decay.span == DecaySpan::Trip, "Each trip" }
+                    }
+                }
+                div { class: "flex items-center justify-between gap-2",
+                    span { class: "text-muted", "Re-dose every (min, 0 = never)" }
+                    input {
+                        "type": "number",
+                        class: "input w-20 h-7 text-center text-sm px-1 py-0",
+                        min: "0",
+                        value: "{redose_value}",
+                        oninput: move |evt| {
+                            if let Ok(minutes) = evt.value().parse::<u32>() {
+                                app_state.write().boost_decay.redose_minutes =
+                                    (minutes > 0).then_some(minutes);
+                            }
+                        },
+                    }
+                }
+                label { class: "flex items-center gap-2 cursor-pointer",
+                    input {
+                        "type": "checkbox",
+                        checked: decay.preserve,
+                        onchange: move |evt| app_state.write().boost_decay.preserve = evt.checked(),
+                    }
+                    span { "Preserve active" }
+                }
+                if span_secs.is_none() {
+                    div { class: "text-subtle text-xs",
+                        "Pick a monster that can be killed to average over the fight."
+                    }
+                }
+
+                if heart_too_often {
+                    div { class: "text-warning text-xs",
+                        "Hearts can only be used every {HEART_COOLDOWN_MINUTES} minutes."
+                    }
+                }
+
+                // Full vs. averaged levels for each boosted or drained stat
+                if let Some(secs) = span_secs.filter(|_| !boosted_skills.is_empty()) {
+                    div { class: "text-subtle text-xs text-center",
+                        {format!("Averaged over {:.1} minutes", secs / 60.0)}
+                    }
+                    div { class: "grid grid-cols-3 gap-x-3 gap-y-1 mx-auto w-fit",
+                        span { class: "text-subtle text-xs", "Skill" }
+                        span { class: "text-subtle text-xs text-right", "Dosed" }
+                        span { class: "text-subtle text-xs text-right", "Average" }
+                        for (skill, base, current) in boosted_skills {
+                            div { class: "flex items-center gap-1",
+                                img {
+                                    class: "w-4 h-4 object-contain",
+                                    src: "{skill.icon_path()}",
+                                    alt: "{skill.name()}",
+                                }
+                                span { "{skill.name()}" }
+                            }
+                            span { class: "text-right", "{current}" }
+                            span { class: "text-right font-bold",
+                                {format!("{:.1}", decay.average_level(base, current, secs))}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use crate::calc::CombatClass;
+use crate::gear_effects::EffectCheck;
+use crate::rates::xp_per_hour;
+use crate::state::AppState;
+use dioxus::prelude::*;
+
+#[component]
+pub fn CombatResults() -> Element {
+    let app_state = use_context::<Signal<AppState>>();
+
+    let Some(stats) = ({
+        let state = app_state.read();
+        state.target().map(|monster| state.fight_stats(&monster))
+    }) else {
+        return rsx! {};
+    };
+    // Kills and experience per hour, including respawns, idle time and banking
+    let rates = {
+        let state = app_state.read();
+        let hitpoints = state.target().map_or(0, |monster| monster.max_hp());
+        state.trip_estimate().map(|estimate| {
+            let xp = xp_per_hour(&state.player, hitpoints, estimate.kills_per_hour);
+            (estimate.kills_per_hour, xp)
+        })
+    };
+
+    let max_hit = stats
+        .offence
+        .max_hit
+        .map_or("–".to_string(), |hit| hit.to_string());
+    let dps = stats.dps.map_or("–".to_string(), |dps| format!("{dps:.3}"));
+    let ttk = stats
+        .ttk_secs
+        .map_or("–".to_string(), |secs| format!("{secs:.1}s"));
+    let needs_spell = stats.offence.class == CombatClass::Magic && stats.offence.max_hit.is_none();
+
+    rsx! {
+        div { class: "panel-elevated p-3 mt-2 text-sm",
+            h4 { class: "text-xs font-semibold text-muted mb-2", "Results" }
+            div { class: "grid grid-cols-2 gap-x-4 gap-y-1",
+                span { class: "text-muted", "Max hit" }
+                span { class: "text-right font-bold", "{max_hit}" }
+                if let Some(spell) = stats.offence.spell {
+                    span { class: "text-muted", "Spell" }
+                    span { class: "text-right",
+                        "{spell.name}"
+                        if stats.on_element {
+                            span {
+                                class: "ml-1 text-[10px] px-1 rounded bg-green-600/30 text-green-300",
+                                title: "Casting the target's elemental weakness",
+                                "on-element"
+                            }
+                        }
+                    }
+                }
+                span { class: "text-muted", "Accuracy" }
+                span {
+                    class: "text-right font-bold",
+                    title: "Attack roll {stats.offence.attack_roll} vs defence roll {stats.defence_roll}",
+                    {format!("{:.2}%", stats.hit_chance * 100.0)}
+                }
+                span { class: "text-muted", "Attack speed" }
+                span { class: "text-right", "{stats.attack_speed} ticks" }
+                span { class: "text-muted", "DPS" }
+                span { class: "text-right font-bold text-accent", "{dps}" }
+                span { class: "text-muted", "Time to kill" }
+                span { class: "text-right font-bold", "{ttk}" }
+                if let Some((kills_per_hour, xp)) = rates {
+                    span { class: "text-muted", "Kills per hour" }
+                    span { class: "text-right font-bold text-accent", {format!("{kills_per_hour:.1}")} }
+                    for (skill, xp_per_hour) in xp {
+                        span { class: "text-muted", "{skill.name()} XP/h" }
+                        span { class: "text-right", {format!("{:.0}", xp_per_hour)} }
+                    }
+                }
+            }
+            for dot in stats.dots.iter() {
+                {
+                    match dot.kind {
+                        Some(kind) => rsx! {
+                            div { class: "text-xs text-success mt-1",
+                                {format!("☠ {} from {}: ~{:.0} damage over the kill", kind.name(), dot.source.item, dot.damage)}
+                            }
+                        },
+                        None => rsx! {
+                            div { class: "text-xs text-warning mt-1",
+                                "⚠ The target is immune to {dot.source.kind.name().to_lowercase()} from {dot.source.item}"
+                            }
+                        },
+                    }
+                }
+            }
+            for check in stats.gear_effects.iter() {
+                {
+                    match check {
+                        EffectCheck::Applies { item, effect } => rsx! {
+                            div { class: "text-xs text-success mt-1",
+                                "✓ {item}: {effect.description()}"
+                            }
+                        },
+                        EffectCheck::WrongStyle { item, attribute } => rsx! {
+                            div { class: "text-xs text-warning mt-1",
+                                "⚠ {item} doesn't boost this style against {attribute.name().to_lowercase()} monsters"
+                            }
+                        },
+                        EffectCheck::WrongTarget { item, attribute } => rsx! {
+                            div { class: "text-xs text-warning mt-1",
+                                "⚠ {item} only has an effect against {attribute.name().to_lowercase()} monsters"
+                            }
+                        },
+                        EffectCheck::CannotHarm { attribute } => rsx! {
+                            div { class: "text-xs text-warning mt-1",
+                                "⚠ {attribute.name()} monsters can only be harmed with a silver or blisterwood weapon for their tier"
+                            }
+                        },
+                    }
+                }
+            }
+            if needs_spell {
+                div { class: "text-xs text-warning mt-2",
+                    "Pick a spell or equip a powered staff to get a magic max hit."
+                }
+            }
+        }
+    }
+}
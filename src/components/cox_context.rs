@@ -0,0 +1,124 @@
+use crate::monster::SelectedMonster;
+use crate::raids::{challenge_mode_counterpart, is_challenge_mode, CoxContext, COX_MAX_PARTY_SIZE};
+use crate::skill::Skill;
+use crate::state::AppState;
+use dioxus::prelude::*;
+
+/// Party size, the party's highest levels and challenge mode for a Chambers of
+/// Xeric monster, shown only while one is selected. Levels default to the
+/// player's own.
+#[component]
+pub fn CoxContextPanel() -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+
+    let (cox, selected, player_combat, player_hitpoints, base_hp, scaled) = {
+        let state = app_state.read();
+        let Some(monster) = state.monster.as_ref() else {
+            return rsx! {};
+        };
+        if !CoxContext::applies_to(&monster.edited) {
+            return rsx! {};
+        }
+        let Some(scaled) = state.target() else {
+            return rsx! {};
+        };
+        (
+            state.cox,
+            monster.edited.clone(),
+            state.combat_level(),
+            state.base_level(Skill::Hitpoints),
+            monster.max_hp(),
+            scaled.edited,
+        )
+    };
+    let challenge_mode = cox.challenge_mode || is_challenge_mode(&selected);
+    let base = selected.stats;
+    let level_value = |level: Option<u32>| level.map_or(String::new(), |level| level.to_string());
+    let parse_level = |value: String| value.trim().parse::<u32>().ok();
+
+    rsx! {
+        div { class: "panel-elevated p-3 mt-2 text-sm",
+            label { class: "flex items-center gap-2 cursor-pointer",
+                input {
+                    "type": "checkbox",
+                    checked: cox.enabled,
+                    onchange: move |evt| app_state.write().cox.enabled = evt.checked(),
+                }
+                span { class: "text-xs font-semibold text-muted", "Chambers of Xeric scaling" }
+            }
+            if cox.enabled {
+                div { class: "flex flex-col gap-1 mt-2 text-xs",
+                    div { class: "flex items-center justify-between gap-2",
+                        span { class: "text-muted", "Party size" }
+                        input {
+                            "type": "number",
+                            class: "input w-20 h-7 text-center text-sm px-1 py-0",
+                            min: "1",
+                            max: "{COX_MAX_PARTY_SIZE}",
+                            value: "{cox.party_size}",
+                            oninput: move |evt| {
+                                if let Some(size) = parse_level(evt.value()) {
+                                    app_state.write().cox.party_size = size.clamp(1, COX_MAX_PARTY_SIZE);
+                                }
+                            },
+                        }
+                    }
+                    div { class: "flex items-center justify-between gap-2",
+                        span { class: "text-muted", "Highest combat level" }
+                        input {
+                            "type": "number",
+                            class: "input w-20 h-7 text-center text-sm px-1 py-0",
+                            min: "3",
+                            max: "126",
+                            placeholder: "{player_combat}",
+                            value: "{level_value(cox.max_combat)}",
+                            oninput: move |evt| app_state.write().cox.max_combat = parse_level(evt.value()),
+                        }
+                    }
+                    div { class: "flex items-center justify-between gap-2",
+                        span { class: "text-muted", "Highest Hitpoints level" }
+                        input {
+                            "type": "number",
+                            class: "input w-20 h-7 text-center text-sm px-1 py-0",
+                            min: "10",
+                            max: "99",
+                            placeholder: "{player_hitpoints}",
+                            value: "{level_value(cox.max_hitpoints)}",
+                            oninput: move |evt| app_state.write().cox.max_hitpoints = parse_level(evt.value()),
+                        }
+                    }
+                    label { class: "flex items-center gap-2 cursor-pointer",
+                        input {
+                            "type": "checkbox",
+                            checked: challenge_mode,
+                            onchange: move |evt| {
+                                let checked = evt.checked();
+                                let mut state = app_state.write();
+                                state.cox.challenge_mode = checked;
+                                // Use the challenge mode stats from monsters.json where listed
+                                if let Some(version) = challenge_mode_counterpart(&selected, checked) {
+                                    state.monster = Some(SelectedMonster::new(version));
+                                }
+                            },
+                        }
+                        span { "Challenge mode" }
+                    }
+                    div { class: "grid grid-cols-3 gap-x-3 gap-y-1 mt-1 text-subtle",
+                        span { "Hitpoints" }
+                        span { class: "text-right", "{base_hp}" }
+                        span { class: "text-right font-bold text-accent", "{scaled.stats.hitpoints}" }
+                        span { "Defence" }
+                        span { class: "text-right", "{base.defence}" }
+                        span { class: "text-right font-bold text-accent", "{scaled.stats.defence}" }
+                        span { "Attack" }
+                        span { class: "text-right", "{base.attack}" }
+                        span { class: "text-right font-bold text-accent", "{scaled.stats.attack}" }
+                    }
+                    div { class: "text-subtle",
+                        "Leave levels blank to use your own: combat {player_combat}, Hitpoints {player_hitpoints}."
+                    }
+                }
+            }
+        }
+    }
+}
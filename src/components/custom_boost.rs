@@ -0,0 +1,127 @@
+use crate::boosts::CustomBoost;
+use crate::skill::{Skill, PLAYER_SKILLS};
+use dioxus::prelude::*;
+
+#[component]
+pub fn CustomBoostForm(disabled: bool, on_add: EventHandler<CustomBoost>) -> Element {
+    let mut is_open = use_signal(|| false);
+    let mut skill = use_signal(|| Skill::Strength);
+    let mut flat = use_signal(|| 0i32);
+    let mut percent = use_signal(|| 0i32);
+
+    let is_empty = flat() == 0 && percent() == 0;
+
+    rsx! {
+        div { class: "max-w-md mx-auto mt-2",
+            if !is_open() {
+                button {
+                    "type": "button",
+                    class: "btn-secondary text-sm w-full py-1",
+                    disabled,
+                    onclick: move |_| is_open.set(true),
+                    "+ Custom boost"
+                }
+            } else {
+                div { class: "panel-elevated p-2 flex flex-col gap-2 text-sm",
+                    div { class: "flex items-center gap-2",
+                        select {
+                            class: "input h-8 py-0 px-2 text-sm flex-grow",
+                            onchange: move |evt| {
+                                if let Some(selected) = PLAYER_SKILLS
+                                    .iter()
+                                    .find(|s| s.name() == evt.value())
+                                {
+                                    skill.set(*selected);
+                                }
+                            },
+                            for option in PLAYER_SKILLS.iter() {
+                                option {
+                                    value: "{option.name()}",
+                                    selected: *option == skill(),
+                                    "{option.name()}"
+                                }
+                            }
+                        }
+                        input {
+                            "type": "number",
+                            class: "input w-14 h-8 text-center text-sm px-1 py-0",
+                            title: "Flat levels",
+                            value: "{flat}",
+                            oninput: move |evt| {
+                                if let Ok(value) = evt.value().parse::<i32>() {
+                                    flat.set(value);
+                                }
+                            },
+                        }
+                        span { "+" }
+                        input {
+                            "type": "number",
+                            class: "input w-14 h-8 text-center text-sm px-1 py-0",
+                            title: "Percentage of base level",
+                            value: "{percent}",
+                            oninput: move |evt| {
+                                if let Ok(value) = evt.value().parse::<i32>() {
+                                    percent.set(value);
+                                }
+                            },
+                        }
+                        span { "%" }
+                    }
+                    div { class: "flex gap-2 justify-end",
+                        button {
+                            "type": "button",
+                            class: "btn-secondary text-sm py-1",
+                            onclick: move |_| is_open.set(false),
+                            "Cancel"
+                        }
+                        button {
+                            "type": "button",
+                            class: "btn-primary text-sm py-1",
+                            disabled: is_empty,
+                            onclick: move |_| {
+                                on_add.call(CustomBoost {
+                                    skill: skill(),
+                                    flat: flat(),
+                                    percent: percent(),
+                                });
+                                flat.set(0);
+                                percent.set(0);
+                                is_open.set(false);
+                            },
+                            "Add"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn CustomBoostSlot(boost: CustomBoost, on_remove: EventHandler<CustomBoost>) -> Element {
+    let label = boost.label();
+    let title = format!("Custom boost: {label} {}", boost.skill.name());
+
+    rsx! {
+        div {
+            class: "relative group",
+            div {
+                class: "equipment-slot-bg flex flex-col justify-center items-center h-[40px] w-[40px] p-1",
+                title: "{title}",
+                img {
+                    class: "h-4 w-4 object-contain",
+                    src: "{boost.skill.icon_path()}",
+                    alt: "{boost.skill.name()}",
+                }
+                span { class: "text-[9px] leading-none mt-0.5 whitespace-nowrap", "{label}" }
+            }
+            button {
+                "type": "button",
+                class: "absolute -top-1 -right-1 w-4 h-4 bg-red-600 hover:bg-red-700 rounded-full flex items-center justify-center opacity-0 group-hover:opacity-100 transition-opacity duration-150",
+                style: "font-size: 10px; line-height: 1; font-family: monospace;",
+                onclick: move |_| on_remove.call(boost),
+                "×"
+            }
+        }
+    }
+}
@@ -0,0 +1,393 @@
+use crate::components::monster_editor::{
+    Field, MonsterField, DEFENCE_FIELDS, OFFENCE_FIELDS, STAT_FIELDS,
+};
+use crate::monster::{parse_monsters, MonsterJson, MonsterWeakness, SelectedMonster};
+use crate::state::AppState;
+use dioxus::prelude::*;
+
+const INFO_FIELDS: [Field; 4] = [
+    (
+        "Combat",
+        |m| m.info.combat_level,
+        |m, v| m.info.combat_level = v,
+    ),
+    (
+        "Speed",
+        |m| m.info.attack_speed,
+        |m, v| m.info.attack_speed = v,
+    ),
+    ("Size", |m| m.info.size, |m, v| m.info.size = v),
+    (
+        "Freeze %",
+        |m| m.immunities.freeze,
+        |m, v| m.immunities.freeze = v,
+    ),
+];
+
+const ELEMENTS: [&str; 4] = ["air", "water", "earth", "fire"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Panel {
+    Closed,
+    NewMonster,
+    ImportExport,
+}
+
+// Comma-separated text as a list, or `None` when it is empty
+fn split_list(text: &str) -> Option<Vec<String>> {
+    let items: Vec<String> = text
+        .split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect();
+    (!items.is_empty()).then_some(items)
+}
+
+fn non_empty(text: &str) -> Option<String> {
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn join_list(items: &Option<Vec<String>>) -> String {
+    items.as_deref().unwrap_or_default().join(", ")
+}
+
+#[component]
+pub fn CustomMonsterPanel() -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+    let mut panel = use_signal(|| Panel::Closed);
+
+    let custom_monsters = app_state.read().custom_monsters.clone();
+
+    rsx! {
+        div { class: "mt-2 flex flex-col gap-2 text-sm",
+            div { class: "flex gap-2",
+                button {
+                    "type": "button",
+                    class: "btn-secondary text-sm flex-grow py-1",
+                    onclick: move |_| {
+                        panel.set(if panel() == Panel::NewMonster { Panel::Closed } else { Panel::NewMonster })
+                    },
+                    "+ New monster"
+                }
+                button {
+                    "type": "button",
+                    class: "btn-secondary text-sm flex-grow py-1",
+                    onclick: move |_| {
+                        panel.set(if panel() == Panel::ImportExport { Panel::Closed } else { Panel::ImportExport })
+                    },
+                    "Import / export"
+                }
+            }
+
+            {
+                match panel() {
+                    Panel::NewMonster => rsx! {
+                        NewMonsterForm { on_close: move |_| panel.set(Panel::Closed) }
+                    },
+                    Panel::ImportExport => rsx! { ImportExportForm {} },
+                    Panel::Closed => rsx! {},
+                }
+            }
+
+            if !custom_monsters.is_empty() {
+                div { class: "flex flex-wrap gap-1",
+                    for (idx, monster) in custom_monsters.into_iter().enumerate() {
+                        div {
+                            key: "{monster.key()}",
+                            class: "relative group",
+                            button {
+                                "type": "button",
+                                class: "btn-secondary text-xs py-1 px-2",
+                                onclick: {
+                                    let monster = monster.clone();
+                                    move |_| app_state.write().monster = Some(SelectedMonster::new(monster.clone()))
+                                },
+                                "{monster.display_name()}"
+                            }
+                            button {
+                                "type": "button",
+                                class: "absolute -top-1 -right-1 w-4 h-4 bg-red-600 hover:bg-red-700 rounded-full flex items-center justify-center opacity-0 group-hover:opacity-100 transition-opacity duration-150",
+                                style: "font-size: 10px; line-height: 1; font-family: monospace;",
+                                title: "Delete this monster",
+                                onclick: move |_| app_state.write().remove_custom_monster(idx),
+                                "×"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn NewMonsterForm(on_close: EventHandler<()>) -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+    let mut draft = use_signal(|| MonsterJson::blank(app_state.peek().next_custom_monster_id()));
+    let mut version = use_signal(String::new);
+    let mut attack_styles = use_signal(String::new);
+    let mut attributes = use_signal(String::new);
+    let mut max_hits = use_signal(String::new);
+    let mut burn = use_signal(String::new);
+    let mut weakness_element = use_signal(String::new);
+    let mut weakness_severity = use_signal(|| 0i32);
+
+    // Copies the selected monster's values into the form
+    let start_from_selected = move |_| {
+        let Some(selected) = app_state.read().monster.as_ref().map(|m| m.edited.clone()) else {
+            return;
+        };
+        version.set(selected.info.version.clone().unwrap_or_default());
+        attack_styles.set(join_list(&selected.info.attack_styles));
+        attributes.set(join_list(&selected.info.attributes));
+        max_hits.set(join_list(&selected.max_hit));
+        burn.set(selected.immunities.burn.clone().unwrap_or_default());
+        weakness_element.set(
+            selected
+                .info
+                .weakness
+                .as_ref()
+                .map(|w| w.element.clone())
+                .unwrap_or_default(),
+        );
+        weakness_severity.set(selected.info.weakness.as_ref().map_or(0, |w| w.severity));
+        let mut copy = selected;
+        copy.info.id = app_state.read().next_custom_monster_id();
+        draft.set(copy);
+    };
+
+    let save = move |_| {
+        let mut monster = draft();
+        monster.info.name = monster.info.name.trim().to_string();
+        monster.info.version = non_empty(&version());
+        monster.info.attack_styles = split_list(&attack_styles());
+        monster.info.attributes = split_list(&attributes.read().to_lowercase());
+        monster.max_hit = split_list(&max_hits());
+        monster.immunities.burn = non_empty(&burn());
+        monster.info.weakness = (!weakness_element().is_empty()).then(|| MonsterWeakness {
+            element: weakness_element(),
+            severity: weakness_severity(),
+        });
+
+        let mut state = app_state.write();
+        state.add_custom_monster(monster.clone());
+        state.monster = Some(SelectedMonster::new(monster));
+        on_close.call(());
+    };
+
+    let monster = draft();
+    let sections: [(&str, &[Field]); 4] = [
+        ("Info", &INFO_FIELDS),
+        ("Stats", &STAT_FIELDS),
+        ("Defence", &DEFENCE_FIELDS),
+        ("Offence", &OFFENCE_FIELDS),
+    ];
+    let text_input = "input w-full h-7 text-sm px-2 py-0";
+
+    rsx! {
+        div { class: "panel-elevated p-3 flex flex-col gap-2",
+            div { class: "flex gap-2",
+                input {
+                    class: "{text_input}",
+                    placeholder: "Name",
+                    value: "{monster.info.name}",
+                    oninput: move |evt| draft.write().info.name = evt.value(),
+                }
+                input {
+                    class: "{text_input}",
+                    placeholder: "Version (optional)",
+                    value: "{version}",
+                    oninput: move |evt| version.set(evt.value()),
+                }
+            }
+
+            for (title, fields) in sections {
+                div {
+                    h5 { class: "text-xs text-subtle mb-1", "{title}" }
+                    div { class: "grid grid-cols-4 gap-1",
+                        for &(label, get, set) in fields {
+                            MonsterField {
+                                label,
+                                value: get(&monster),
+                                is_edited: false,
+                                on_change: move |value: i32| set(&mut draft.write(), value),
+                            }
+                        }
+                    }
+                }
+            }
+
+            input {
+                class: "{text_input}",
+                placeholder: "Attack styles, e.g. Crush, Magic",
+                value: "{attack_styles}",
+                oninput: move |evt| attack_styles.set(evt.value()),
+            }
+            input {
+                class: "{text_input}",
+                placeholder: "Max hits, e.g. 30 (Melee), 45 (Magic)",
+                value: "{max_hits}",
+                oninput: move |evt| max_hits.set(evt.value()),
+            }
+            input {
+                class: "{text_input}",
+                placeholder: "Attributes, e.g. demon, undead",
+                value: "{attributes}",
+                oninput: move |evt| attributes.set(evt.value()),
+            }
+
+            div { class: "flex items-center gap-2",
+                span { class: "text-muted text-xs", "Weakness" }
+                select {
+                    class: "input h-7 py-0 px-2 text-sm",
+                    onchange: move |evt| weakness_element.set(evt.value()),
+                    option { value: "", selected: weakness_element().is_empty(), "None" }
+                    for element in ELEMENTS {
+                        option {
+                            value: "{element}",
+                            selected: weakness_element() == element,
+                            "{element}"
+                        }
+                    }
+                }
+                if !weakness_element().is_empty() {
+                    input {
+                        "type": "number",
+                        class: "input w-14 h-7 text-center text-sm px-1 py-0",
+                        title: "Severity (%)",
+                        value: "{weakness_severity}",
+                        oninput: move |evt| {
+                            if let Ok(severity) = evt.value().parse::<i32>() {
+                                weakness_severity.set(severity);
+                            }
+                        },
+                    }
+                    span { class: "text-xs text-muted", "%" }
+                }
+            }
+
+            div { class: "flex flex-wrap items-center gap-3",
+                label { class: "flex items-center gap-1 cursor-pointer",
+                    input {
+                        "type": "checkbox",
+                        checked: monster.immunities.poison,
+                        onchange: move |evt| draft.write().immunities.poison = evt.checked(),
+                    }
+                    span { "Poison immune" }
+                }
+                label { class: "flex items-center gap-1 cursor-pointer",
+                    input {
+                        "type": "checkbox",
+                        checked: monster.immunities.venom,
+                        onchange: move |evt| draft.write().immunities.venom = evt.checked(),
+                    }
+                    span { "Venom immune" }
+                }
+            }
+            input {
+                class: "{text_input}",
+                placeholder: "Burn immunity, e.g. Immune to weak burns",
+                value: "{burn}",
+                oninput: move |evt| burn.set(evt.value()),
+            }
+
+            div { class: "flex gap-2",
+                button {
+                    "type": "button",
+                    class: "btn-primary text-sm flex-grow py-1",
+                    disabled: monster.info.name.trim().is_empty(),
+                    onclick: save,
+                    "Save monster"
+                }
+                button {
+                    "type": "button",
+                    class: "btn-secondary text-sm py-1 px-3",
+                    disabled: app_state.read().monster.is_none(),
+                    title: "Copy the selected monster's values",
+                    onclick: start_from_selected,
+                    "Copy selected"
+                }
+                button {
+                    "type": "button",
+                    class: "btn-secondary text-sm py-1 px-3",
+                    onclick: move |_| on_close.call(()),
+                    "Cancel"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ImportExportForm() -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+    let mut text = use_signal(String::new);
+    let mut message = use_signal(|| None::<Result<String, String>>);
+
+    let export = move |_| {
+        let monsters = app_state.read().custom_monsters.clone();
+        match serde_json::to_string_pretty(&monsters) {
+            Ok(json) => {
+                text.set(json);
+                message.set(Some(Ok(format!("Exported {} monsters", monsters.len()))));
+            }
+            Err(e) => message.set(Some(Err(e.to_string()))),
+        }
+    };
+
+    let import = move |_| {
+        let result = parse_monsters(&text.read());
+        match result {
+            Ok(monsters) => {
+                let count = monsters.len();
+                let mut state = app_state.write();
+                for mut monster in monsters {
+                    // Keep imported entries apart from monsters.json ids
+                    if monster.info.id >= 0 {
+                        monster.info.id = state.next_custom_monster_id();
+                    }
+                    state.add_custom_monster(monster);
+                }
+                message.set(Some(Ok(format!("Imported {count} monsters"))));
+            }
+            Err(e) => message.set(Some(Err(format!("Invalid monster JSON: {e}")))),
+        }
+    };
+
+    rsx! {
+        div { class: "panel-elevated p-3 flex flex-col gap-2",
+            span { class: "text-xs text-muted",
+                "Paste one monster or a list in the monsters.json format, or export your custom monsters."
+            }
+            textarea {
+                class: "input w-full h-40 text-xs font-mono",
+                value: "{text}",
+                oninput: move |evt| text.set(evt.value()),
+            }
+            {
+                match message() {
+                    Some(Ok(note)) => rsx! { div { class: "text-xs text-success", "{note}" } },
+                    Some(Err(error)) => rsx! { div { class: "text-xs text-red-300", "{error}" } },
+                    None => rsx! {},
+                }
+            }
+            div { class: "flex gap-2",
+                button {
+                    "type": "button",
+                    class: "btn-primary text-sm flex-grow py-1",
+                    disabled: text.read().trim().is_empty(),
+                    onclick: import,
+                    "Import"
+                }
+                button {
+                    "type": "button",
+                    class: "btn-secondary text-sm flex-grow py-1",
+                    disabled: app_state.read().custom_monsters.is_empty(),
+                    onclick: export,
+                    "Export"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use crate::defence::{
+    attack_styles, average_damage_per_tick, monster_attacks, Antifire, MonsterStyle, Protection,
+};
+use crate::state::AppState;
+use dioxus::prelude::*;
+
+/// The selected monster's hit chance and expected damage against the player,
+/// per attack style.
+#[component]
+pub fn DefenceResults() -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+
+    let (attacks, styles, protection, antifire, hitpoints) = {
+        let state = app_state.read();
+        let Some(monster) = state.target() else {
+            return rsx! {};
+        };
+        (
+            monster_attacks(
+                &state.player,
+                &monster.edited,
+                state.protection,
+                state.antifire,
+            ),
+            attack_styles(&monster.edited),
+            state.protection,
+            state.antifire,
+            state.player.stats.hitpoints.current,
+        )
+    };
+    let breathes_fire = styles.contains(&MonsterStyle::Dragonfire);
+    let unlisted = styles.len() - attacks.len();
+    let per_tick = average_damage_per_tick(&attacks);
+    // Ticks until the expected damage taken adds up to the player's hitpoints
+    let survival = (per_tick > 0.0).then(|| hitpoints as f64 / per_tick);
+
+    rsx! {
+        div { class: "panel-elevated p-3 mt-2 text-sm",
+            div { class: "flex items-center justify-between mb-2",
+                h4 { class: "text-xs font-semibold text-muted", "Damage taken" }
+                select {
+                    class: "input h-7 py-0 px-2 text-xs",
+                    onchange: move |evt| {
+                        app_state.write().protection = Protection::ALL
+                            .into_iter()
+                            .find(|prayer| prayer.name() == evt.value());
+                    },
+                    option { value: "", selected: protection.is_none(), "No protection" }
+                    for prayer in Protection::ALL {
+                        option {
+                            value: "{prayer.name()}",
+                            selected: protection == Some(prayer),
+                            "{prayer.name()}"
+                        }
+                    }
+                }
+            }
+            if breathes_fire {
+                div { class: "flex items-center justify-between mb-2",
+                    span { class: "text-xs text-muted", "Dragonfire" }
+                    select {
+                        class: "input h-7 py-0 px-2 text-xs",
+                        onchange: move |evt| {
+                            app_state.write().antifire = Antifire::ALL
+                                .into_iter()
+                                .find(|potion| potion.name() == evt.value())
+                                .unwrap_or_default();
+                        },
+                        for potion in Antifire::ALL {
+                            option {
+                                value: "{potion.name()}",
+                                selected: antifire == potion,
+                                "{potion.name()}"
+                            }
+                        }
+                    }
+                }
+            }
+            if attacks.is_empty() {
+                div { class: "text-xs text-subtle", "This monster has no attack styles with a known max hit." }
+            } else {
+                div { class: "grid grid-cols-4 gap-x-3 gap-y-1 text-xs",
+                    span { class: "text-muted", "Style" }
+                    span { class: "text-muted text-right", "Max" }
+                    span { class: "text-muted text-right", "Accuracy" }
+                    span { class: "text-muted text-right", "Per tick" }
+                    for attack in attacks.iter() {
+                        span { "{attack.style.name()}" }
+                        span { class: "text-right", "{attack.max_hit}" }
+                        span {
+                            class: "text-right",
+                            title: "Attack roll {attack.attack_roll} vs defence roll {attack.defence_roll}",
+                            {format!("{:.2}%", attack.hit_chance * 100.0)}
+                        }
+                        span {
+                            class: "text-right",
+                            class: if attack.protected { "text-success" },
+                            title: {format!("{:.2} per second", attack.damage_per_second())},
+                            if attack.protected { "Prayed" } else { {format!("{:.2}", attack.damage_per_tick)} }
+                        }
+                    }
+                }
+                div { class: "grid grid-cols-2 gap-x-4 gap-y-1 mt-2",
+                    span { class: "text-muted", "Expected per tick" }
+                    span { class: "text-right font-bold text-accent", {format!("{per_tick:.2}")} }
+                    span { class: "text-muted", "Hitpoints last" }
+                    span { class: "text-right font-bold",
+                        {survival.map_or("–".to_string(), |ticks| format!("{ticks:.0} ticks"))}
+                    }
+                }
+                if attacks.len() > 1 {
+                    div { class: "text-xs text-subtle mt-1",
+                        "Assumes the monster uses each of its styles equally often."
+                    }
+                }
+                if unlisted > 0 {
+                    div { class: "text-xs text-subtle mt-1",
+                        "Styles without a listed max hit are left out."
+                    }
+                }
+                if breathes_fire {
+                    div { class: "text-xs text-subtle mt-1",
+                        "Dragonfire follows the rules for ordinary dragons, not bosses."
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,349 @@
+use crate::monster::{MonsterJson, SelectedMonster, MONSTERS};
+use crate::state::AppState;
+use crate::timers;
+use dioxus::prelude::*;
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+/// Monsters evaluated between yields to the UI.
+const CHUNK_SIZE: usize = 100;
+/// Height of a table row in pixels. Rows must not wrap for the virtualized
+/// list to line up.
+const ROW_HEIGHT: usize = 28;
+/// Rows tall the scrolling area is.
+const VISIBLE_ROWS: usize = 16;
+/// Rows rendered beyond each edge of the scrolling area.
+const OVERSCAN: usize = 4;
+
+const WEAKNESSES: [&str; 4] = ["Air", "Water", "Earth", "Fire"];
+
+/// The loadout's output against one monster.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct DpsRow {
+    /// Index into `MONSTERS` followed by the custom monsters.
+    idx: usize,
+    max_hit: Option<u32>,
+    hit_chance: f64,
+    dps: Option<f64>,
+    ttk_secs: Option<f64>,
+}
+
+impl DpsRow {
+    fn monster<'a>(&self, custom: &'a [MonsterJson]) -> &'a MonsterJson {
+        monster_at(custom, self.idx)
+    }
+}
+
+/// Entry `idx` of monsters.json followed by the custom monsters.
+fn monster_at(custom: &[MonsterJson], idx: usize) -> &MonsterJson {
+    match MONSTERS.get(idx) {
+        Some(monster) => monster,
+        None => &custom[idx - MONSTERS.len()],
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SortColumn {
+    Name,
+    Level,
+    MaxHit,
+    Accuracy,
+    Dps,
+    Ttk,
+}
+
+impl SortColumn {
+    const ALL: [SortColumn; 6] = [
+        SortColumn::Name,
+        SortColumn::Level,
+        SortColumn::MaxHit,
+        SortColumn::Accuracy,
+        SortColumn::Dps,
+        SortColumn::Ttk,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            SortColumn::Name => "Monster",
+            SortColumn::Level => "Lvl",
+            SortColumn::MaxHit => "Max",
+            SortColumn::Accuracy => "Acc",
+            SortColumn::Dps => "DPS",
+            SortColumn::Ttk => "TTK",
+        }
+    }
+
+    // Unkillable monsters sort after every other row either way
+    fn compare(&self, a: &DpsRow, b: &DpsRow, custom: &[MonsterJson]) -> Ordering {
+        let by_option = |a: Option<f64>, b: Option<f64>| match (a, b) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            (a, b) => b.is_some().cmp(&a.is_some()),
+        };
+        let (monster_a, monster_b) = (a.monster(custom), b.monster(custom));
+        match self {
+            SortColumn::Name => monster_a.display_name().cmp(&monster_b.display_name()),
+            SortColumn::Level => monster_a
+                .info
+                .combat_level
+                .cmp(&monster_b.info.combat_level),
+            SortColumn::MaxHit => a.max_hit.cmp(&b.max_hit),
+            SortColumn::Accuracy => a.hit_chance.total_cmp(&b.hit_chance),
+            SortColumn::Dps => by_option(a.dps, b.dps),
+            SortColumn::Ttk => by_option(a.ttk_secs, b.ttk_secs),
+        }
+    }
+}
+
+/// Filters on the monsters listed.
+#[derive(Debug, Clone, PartialEq, Default)]
+struct Filters {
+    min_level: Option<i32>,
+    max_level: Option<i32>,
+    /// Attribute name, or empty for any.
+    attribute: String,
+    /// Weakness element, or empty for any.
+    weakness: String,
+}
+
+impl Filters {
+    fn matches(&self, monster: &MonsterJson) -> bool {
+        let level = monster.info.combat_level;
+        self.min_level.is_none_or(|min| level >= min)
+            && self.max_level.is_none_or(|max| level <= max)
+            && (self.attribute.is_empty()
+                || monster
+                    .attributes()
+                    .iter()
+                    .any(|attribute| attribute.name() == self.attribute))
+            && (self.weakness.is_empty()
+                || monster
+                    .info
+                    .weakness
+                    .as_ref()
+                    .is_some_and(|weakness| weakness.element.eq_ignore_ascii_case(&self.weakness)))
+    }
+}
+
+fn evaluate(state: &AppState, idx: usize) -> DpsRow {
+    let monster = monster_at(&state.custom_monsters, idx).clone();
+    let monster = state.scaled(SelectedMonster::new(monster));
+    let stats = state.fight_stats(&monster);
+    DpsRow {
+        idx,
+        max_hit: stats.offence.max_hit,
+        hit_chance: stats.hit_chance,
+        dps: stats.dps,
+        ttk_secs: stats.ttk_secs,
+    }
+}
+
+/// The current loadout against every monster in monsters.json and every
+/// custom monster, sortable and filterable. Rows are computed a chunk at a time so the page stays
+/// responsive, and only the rows in view are rendered.
+#[component]
+pub fn DpsTable() -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+    let mut is_collapsed = use_signal(|| true);
+    let mut rows = use_signal(Vec::<DpsRow>::new);
+    let mut running = use_signal(|| None::<Task>);
+    let mut sort = use_signal(|| (SortColumn::Dps, true));
+    let mut filters = use_signal(Filters::default);
+    let mut scroll_top = use_signal(|| 0usize);
+
+    let custom = use_memo(move || app_state.read().custom_monsters.clone());
+    let attribute_names = use_memo(move || {
+        MONSTERS
+            .iter()
+            .chain(custom.read().iter())
+            .flat_map(|monster| monster.attributes())
+            .map(|attribute| attribute.name())
+            .collect::<BTreeSet<_>>()
+    });
+
+    // Only the loadout, spell, raid scaling, boost decay and the custom
+    // monsters change the results, not the selected target. Trips set the
+    // span boosts decay over.
+    let inputs = use_memo(move || {
+        let state = app_state.read();
+        (
+            custom(),
+            state.player.clone(),
+            state.spell,
+            state.toa,
+            state.cox,
+            state.boost_decay,
+            state.trip,
+            state.protection,
+            state.antifire,
+        )
+    });
+
+    // Start over whenever the loadout changes, dropping any run in progress
+    use_effect(move || {
+        let _ = inputs.read();
+        let collapsed = is_collapsed();
+        if let Some(task) = running.take() {
+            task.cancel();
+        }
+        rows.set(Vec::new());
+        if collapsed {
+            return;
+        }
+        let total = MONSTERS.len() + custom.peek().len();
+        running.set(Some(spawn(async move {
+            for start in (0..total).step_by(CHUNK_SIZE) {
+                let end = (start + CHUNK_SIZE).min(total);
+                let chunk: Vec<DpsRow> = {
+                    let state = app_state.peek();
+                    (start..end).map(|idx| evaluate(&state, idx)).collect()
+                };
+                rows.write().extend(chunk);
+                timers::yield_now().await;
+            }
+            running.set(None);
+        })));
+    });
+
+    let listed = use_memo(move || {
+        let (column, descending) = sort();
+        let filters = filters.read();
+        let custom = custom.read();
+        let mut listed: Vec<DpsRow> = rows
+            .read()
+            .iter()
+            .filter(|row| filters.matches(row.monster(&custom)))
+            .copied()
+            .collect();
+        listed.sort_by(|a, b| {
+            if descending {
+                column.compare(b, a, &custom)
+            } else {
+                column.compare(a, b, &custom)
+            }
+        });
+        listed
+    });
+
+    let computed = rows.read().len();
+    let total = MONSTERS.len() + custom.read().len();
+    let listed_count = listed.read().len();
+    let first = (scroll_top() / ROW_HEIGHT).saturating_sub(OVERSCAN);
+    let last = (first + VISIBLE_ROWS + 2 * OVERSCAN).min(listed_count);
+    let first = first.min(last);
+    let visible: Vec<(DpsRow, MonsterJson)> = {
+        let custom = custom.read();
+        listed.read()[first..last]
+            .iter()
+            .map(|row| (*row, row.monster(&custom).clone()))
+            .collect()
+    };
+    let format_ttk = |ttk: Option<f64>| ttk.map_or("–".to_string(), |secs| format!("{secs:.1}s"));
+    let parse_level = |value: String| value.trim().parse::<i32>().ok();
+
+    rsx! {
+        div { class: "w-full",
+            // Toggle header
+            div {
+                class: "flex items-center justify-between cursor-pointer p-2 hover:bg-gray-800 rounded transition-colors",
+                onclick: move |_| is_collapsed.set(!is_collapsed()),
+                h3 { class: "text-sm font-semibold text-muted", "Against every monster" }
+                span {
+                    class: "text-muted transition-transform duration-200",
+                    class: if !is_collapsed() { "rotate-180" },
+                    "▼"
+                }
+            }
+
+            if !is_collapsed() {
+                div { class: "flex flex-col gap-2 mt-2 text-sm",
+                    div { class: "flex flex-wrap items-center gap-2 text-xs",
+                        span { class: "text-muted", "Level" }
+                        input {
+                            class: "input h-7 w-16 py-0 px-2 text-xs",
+                            "type": "number",
+                            placeholder: "min",
+                            oninput: move |evt| filters.write().min_level = parse_level(evt.value()),
+                        }
+                        input {
+                            class: "input h-7 w-16 py-0 px-2 text-xs",
+                            "type": "number",
+                            placeholder: "max",
+                            oninput: move |evt| filters.write().max_level = parse_level(evt.value()),
+                        }
+                        select {
+                            class: "input h-7 py-0 px-2 text-xs",
+                            onchange: move |evt| filters.write().attribute = evt.value(),
+                            option { value: "", "Any attribute" }
+                            for name in attribute_names.read().iter() {
+                                option { value: "{name}", "{name}" }
+                            }
+                        }
+                        select {
+                            class: "input h-7 py-0 px-2 text-xs",
+                            onchange: move |evt| filters.write().weakness = evt.value(),
+                            option { value: "", "Any weakness" }
+                            for element in WEAKNESSES {
+                                option { value: "{element}", "{element}" }
+                            }
+                        }
+                    }
+
+                    div { class: "text-xs text-subtle",
+                        if computed < total {
+                            "Calculating… {computed} of {total} monsters"
+                        } else {
+                            "{listed_count} of {total} monsters"
+                        }
+                    }
+
+                    div { class: "grid grid-cols-[1fr_3rem_3rem_4rem_4rem_4rem] gap-x-2 px-2 text-xs font-semibold text-muted",
+                        for column in SortColumn::ALL {
+                            button {
+                                "type": "button",
+                                class: "text-left hover:text-accent",
+                                class: if sort().0 == column { "text-accent" },
+                                onclick: move |_| {
+                                    let (current, descending) = sort();
+                                    sort.set((column, current != column || !descending));
+                                },
+                                "{column.label()}"
+                                if sort().0 == column {
+                                    if sort().1 { " ▼" } else { " ▲" }
+                                }
+                            }
+                        }
+                    }
+
+                    div {
+                        class: "overflow-y-auto",
+                        style: "height: {VISIBLE_ROWS * ROW_HEIGHT}px",
+                        onscroll: move |evt| scroll_top.set(evt.scroll_top().max(0.0) as usize),
+                        div { style: "height: {listed_count * ROW_HEIGHT}px; position: relative",
+                            for (offset, (row, monster)) in visible.into_iter().enumerate() {
+                                button {
+                                    key: "{row.idx}",
+                                    "type": "button",
+                                    class: "grid grid-cols-[1fr_3rem_3rem_4rem_4rem_4rem] gap-x-2 items-center w-full px-2 text-xs text-left rounded hover:bg-gray-700",
+                                    style: "position: absolute; top: {(first + offset) * ROW_HEIGHT}px; height: {ROW_HEIGHT}px",
+                                    title: "Set as the target",
+                                    onclick: {
+                                        let monster = monster.clone();
+                                        move |_| app_state.write().monster = Some(SelectedMonster::new(monster.clone()))
+                                    },
+                                    span { class: "truncate", "{monster.display_name()}" }
+                                    span { class: "text-muted", "{monster.info.combat_level}" }
+                                    span { {row.max_hit.map_or("–".to_string(), |hit| hit.to_string())} }
+                                    span { {format!("{:.1}%", row.hit_chance * 100.0)} }
+                                    span { class: "font-bold text-accent",
+                                        {row.dps.map_or("–".to_string(), |dps| format!("{dps:.3}"))}
+                                    }
+                                    span { {format_ttk(row.ttk_secs)} }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
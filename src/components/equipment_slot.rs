@@ -1,6 +1,8 @@
 use crate::state::AppState;
+use crate::unlocks::Unlock;
 use dioxus::prelude::*;
 use osrs::types::equipment::GearSlot;
+use strum::IntoEnumIterator;
 
 #[component]
 pub fn EquipmentGridSlot(slot_type: GearSlot) -> Element {
@@ -10,9 +12,18 @@ pub fn EquipmentGridSlot(slot_type: GearSlot) -> Element {
         Some(ref item) => item.name(),
         None => "",
     };
+    let is_locked = {
+        let state = state.read();
+        Unlock::iter().any(|unlock| unlock.covers_item(item_name) && !state.is_unlocked(unlock))
+    };
+    let title = if is_locked {
+        format!("{item_name} (not unlocked)")
+    } else {
+        item_name.to_string()
+    };
     let placeholder_image = format!("{}/{slot_type}.png", crate::PLACEHOLDERS_ASSETS);
     let button_class = format!(
-        "equipment-slot-bg flex justify-center items-center h-[40px] w-[40px] {}",
+        "equipment-slot-bg relative flex justify-center items-center h-[40px] w-[40px] {}",
         if current_item.is_some() {
             "cursor-pointer"
         } else {
@@ -24,7 +35,7 @@ pub fn EquipmentGridSlot(slot_type: GearSlot) -> Element {
         button {
             "type": "button",
             class: "{button_class}",
-            title: "{item_name}",
+            title: "{title}",
             onmousedown: move |_| {
                 if current_item.is_some() {
                     state.write().player.unequip_slot(&slot_type);
@@ -51,6 +62,11 @@ pub fn EquipmentGridSlot(slot_type: GearSlot) -> Element {
                     }
                 }
             }
+            if is_locked {
+                span { class: "absolute -bottom-1 -right-1 text-[10px] leading-none pointer-events-none",
+                    "🔒"
+                }
+            }
         }
     }
 }
@@ -1,13 +1,41 @@
+mod activities;
+mod boost_decay;
+mod combat_results;
+mod cox_context;
+mod custom_boost;
+mod custom_monster;
+mod defence_results;
+mod dps_table;
 mod equipment_grid;
 mod equipment_select;
 mod equipment_slot;
+mod monster_editor;
+mod monster_select;
+mod monster_versions;
+mod paste_stats;
 mod potions;
 mod prayers;
+mod profiles;
 mod search_bar;
 mod skills;
+mod spell_select;
+mod toa_context;
+mod training_planner;
+mod trip_results;
 
+pub use combat_results::CombatResults;
+pub use cox_context::CoxContextPanel;
+pub use custom_monster::CustomMonsterPanel;
+pub use defence_results::DefenceResults;
+pub use dps_table::DpsTable;
 pub use equipment_grid::EquipmentGrid;
 pub use equipment_select::EquipmentSelect;
+pub use monster_editor::MonsterEditor;
+pub use monster_select::MonsterSelect;
+pub use monster_versions::MonsterVersions;
 pub use potions::PotionSelect;
 pub use prayers::PrayerSelect;
 pub use skills::SkillsSelect;
+pub use spell_select::SpellSelect;
+pub use toa_context::ToaContextPanel;
+pub use trip_results::TripResults;
@@ -0,0 +1,285 @@
+use crate::monster::{BurnStrength, MonsterJson};
+use crate::state::AppState;
+use dioxus::prelude::*;
+
+/// Label, getter and setter of an editable monster value.
+pub type Field = (
+    &'static str,
+    fn(&MonsterJson) -> i32,
+    fn(&mut MonsterJson, i32),
+);
+
+pub const STAT_FIELDS: [Field; 6] = [
+    ("Attack", |m| m.stats.attack, |m, v| m.stats.attack = v),
+    (
+        "Strength",
+        |m| m.stats.strength,
+        |m, v| m.stats.strength = v,
+    ),
+    ("Defence", |m| m.stats.defence, |m, v| m.stats.defence = v),
+    ("Magic", |m| m.stats.magic, |m, v| m.stats.magic = v),
+    ("Ranged", |m| m.stats.ranged, |m, v| m.stats.ranged = v),
+    (
+        "Hitpoints",
+        |m| m.stats.hitpoints,
+        |m, v| m.stats.hitpoints = v,
+    ),
+];
+
+pub const DEFENCE_FIELDS: [Field; 7] = [
+    (
+        "Stab",
+        |m| m.bonuses.defence.stab,
+        |m, v| m.bonuses.defence.stab = v,
+    ),
+    (
+        "Slash",
+        |m| m.bonuses.defence.slash,
+        |m, v| m.bonuses.defence.slash = v,
+    ),
+    (
+        "Crush",
+        |m| m.bonuses.defence.crush,
+        |m, v| m.bonuses.defence.crush = v,
+    ),
+    (
+        "Magic",
+        |m| m.bonuses.defence.magic,
+        |m, v| m.bonuses.defence.magic = v,
+    ),
+    (
+        "Light",
+        |m| m.bonuses.defence.light,
+        |m, v| m.bonuses.defence.light = v,
+    ),
+    (
+        "Standard",
+        |m| m.bonuses.defence.standard,
+        |m, v| m.bonuses.defence.standard = v,
+    ),
+    (
+        "Heavy",
+        |m| m.bonuses.defence.heavy,
+        |m, v| m.bonuses.defence.heavy = v,
+    ),
+];
+
+pub const OFFENCE_FIELDS: [Field; 7] = [
+    (
+        "Melee att",
+        |m| m.bonuses.attack.melee,
+        |m, v| m.bonuses.attack.melee = v,
+    ),
+    (
+        "Ranged att",
+        |m| m.bonuses.attack.ranged,
+        |m, v| m.bonuses.attack.ranged = v,
+    ),
+    (
+        "Magic att",
+        |m| m.bonuses.attack.magic,
+        |m, v| m.bonuses.attack.magic = v,
+    ),
+    (
+        "Melee str",
+        |m| m.bonuses.strength.melee,
+        |m, v| m.bonuses.strength.melee = v,
+    ),
+    (
+        "Ranged str",
+        |m| m.bonuses.strength.ranged,
+        |m, v| m.bonuses.strength.ranged = v,
+    ),
+    (
+        "Magic dmg",
+        |m| m.bonuses.strength.magic,
+        |m, v| m.bonuses.strength.magic = v,
+    ),
+    (
+        "Flat armour",
+        |m| m.bonuses.flat_armour,
+        |m, v| m.bonuses.flat_armour = v,
+    ),
+];
+
+#[component]
+pub fn MonsterEditor() -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+    let mut is_collapsed = use_signal(|| true);
+
+    let Some(monster) = app_state.read().monster.clone() else {
+        return rsx! {
+            div { class: "text-sm text-subtle text-center mt-2", "No monster selected." }
+        };
+    };
+    let is_custom = app_state
+        .read()
+        .custom_monsters
+        .iter()
+        .any(|custom| custom.same_entry(&monster.wiki));
+    let attributes = monster.edited.attributes();
+    let immunities = &monster.edited.immunities;
+    let mut immunity_labels = Vec::new();
+    if immunities.poison {
+        immunity_labels.push("Poison immune".to_string());
+    }
+    if immunities.venom {
+        immunity_labels.push("Venom immune".to_string());
+    }
+    match immunities.burn_immunity() {
+        Some(BurnStrength::Strong) => immunity_labels.push("Burn immune".to_string()),
+        Some(strength) => immunity_labels.push(format!("Immune to {} burns", strength.name())),
+        None => {}
+    }
+    let max_hp = monster.max_hp();
+    let hp_percent = if max_hp > 0 {
+        monster.current_hp * 100 / max_hp
+    } else {
+        0
+    };
+    let sections: [(&str, &[Field]); 3] = [
+        ("Stats", &STAT_FIELDS),
+        ("Defence", &DEFENCE_FIELDS),
+        ("Offence", &OFFENCE_FIELDS),
+    ];
+
+    rsx! {
+        div { class: "panel-elevated p-3 mt-2 flex flex-col gap-2 text-sm",
+            div { class: "flex items-center justify-between gap-2",
+                div {
+                    div { class: "font-semibold flex items-center gap-2",
+                        "{monster.edited.display_name()}"
+                        if is_custom {
+                            span { class: "text-[10px] px-1 rounded bg-amber-600/30 text-amber-300", "custom" }
+                        }
+                    }
+                    div { class: "text-xs text-muted",
+                        "Combat {monster.edited.info.combat_level} · Size {monster.edited.info.size}"
+                    }
+                    if let Some(weakness) = &monster.edited.info.weakness {
+                        div { class: "text-xs text-accent",
+                            "Weak to {weakness.element} ({weakness.severity}%)"
+                        }
+                    }
+                    if !immunity_labels.is_empty() {
+                        div { class: "flex flex-wrap gap-1 mt-1",
+                            for label in immunity_labels {
+                                span { class: "text-[10px] px-1 rounded bg-red-600/20 text-red-300", "{label}" }
+                            }
+                        }
+                    }
+                    if !attributes.is_empty() {
+                        div { class: "flex flex-wrap gap-1 mt-1",
+                            for attribute in attributes {
+                                span { class: "text-[10px] px-1 rounded bg-gray-700 text-muted",
+                                    "{attribute.name()}"
+                                }
+                            }
+                        }
+                    }
+                }
+                if monster.is_edited() {
+                    button {
+                        "type": "button",
+                        class: "btn-secondary text-xs py-1 px-2",
+                        title: "Restore the wiki values",
+                        onclick: move |_| {
+                            if let Some(monster) = app_state.write().monster.as_mut() {
+                                monster.reset();
+                            }
+                        },
+                        "↺ Reset"
+                    }
+                }
+            }
+
+            // Current hitpoints, for partly damaged phases and HP-based mechanics
+            div { class: "flex items-center gap-2",
+                span { class: "text-muted w-20", "Current HP" }
+                input {
+                    "type": "number",
+                    class: "input w-16 h-7 text-center text-sm px-1 py-0",
+                    min: "0",
+                    max: "{max_hp}",
+                    value: "{monster.current_hp}",
+                    oninput: move |evt| {
+                        if let Ok(hp) = evt.value().parse::<u32>() {
+                            if let Some(monster) = app_state.write().monster.as_mut() {
+                                monster.current_hp = hp.min(monster.max_hp());
+                            }
+                        }
+                    },
+                }
+                span { class: "text-subtle", "/ {max_hp}" }
+                div { class: "flex-grow h-2 rounded bg-gray-700 overflow-hidden",
+                    div { class: "h-full bg-green-600", style: "width: {hp_percent}%" }
+                }
+            }
+
+            div {
+                class: "flex items-center justify-between cursor-pointer hover:bg-gray-800 rounded p-1 transition-colors",
+                onclick: move |_| is_collapsed.set(!is_collapsed()),
+                span { class: "text-xs font-semibold text-muted", "Stats & bonuses" }
+                div {
+                    class: "text-xs text-gray-400 transform transition-transform",
+                    class: if is_collapsed() { "" } else { "rotate-180" },
+                    "▼"
+                }
+            }
+
+            if !is_collapsed() {
+                for (title, fields) in sections {
+                    div {
+                        h5 { class: "text-xs text-subtle mb-1", "{title}" }
+                        div { class: "grid grid-cols-4 gap-1",
+                            for &(label, get, set) in fields {
+                                MonsterField {
+                                    label,
+                                    value: get(&monster.edited),
+                                    is_edited: get(&monster.edited) != get(&monster.wiki),
+                                    on_change: move |value: i32| {
+                                        if let Some(monster) = app_state.write().monster.as_mut() {
+                                            // A monster at full health stays at full health
+                                            let was_full = monster.current_hp == monster.max_hp();
+                                            set(&mut monster.edited, value);
+                                            monster.current_hp = if was_full {
+                                                monster.max_hp()
+                                            } else {
+                                                monster.current_hp.min(monster.max_hp())
+                                            };
+                                        }
+                                    },
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn MonsterField(
+    label: &'static str,
+    value: i32,
+    is_edited: bool,
+    on_change: EventHandler<i32>,
+) -> Element {
+    rsx! {
+        label { class: "flex flex-col items-center text-xs text-muted",
+            span { class: "whitespace-nowrap", "{label}" }
+            input {
+                "type": "number",
+                class: "input w-14 h-6 text-center text-sm px-1 py-0",
+                class: if is_edited { "border-amber-500" } else { "" },
+                value: "{value}",
+                oninput: move |evt| {
+                    if let Ok(value) = evt.value().parse::<i32>() {
+                        on_change.call(value);
+                    }
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use crate::components::search_bar::SearchBar;
+use crate::monster::{MonsterJson, SelectedMonster, MONSTERS};
+use crate::state::AppState;
+use dioxus::prelude::*;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+/// A search result: every version of a monster under one name, marking
+/// monsters the user made.
+#[derive(Debug, Clone, PartialEq)]
+struct MonsterEntry {
+    monster: MonsterJson,
+    versions: Vec<String>,
+    is_custom: bool,
+}
+
+fn filter_monster(entry: &MonsterEntry, term: &str) -> bool {
+    entry.monster.info.name.to_lowercase().contains(term)
+        || entry
+            .versions
+            .iter()
+            .any(|version| version.to_lowercase().contains(term))
+}
+
+fn render_monster_item(entry: &MonsterEntry) -> Element {
+    let info = &entry.monster.info;
+    rsx! {
+        div { class: "flex items-center justify-between h-10 gap-3 px-4 py-3 text-sm",
+            div { class: "flex-grow",
+                div { class: "font-small flex items-center gap-2",
+                    "{info.name}"
+                    if entry.is_custom {
+                        span { class: "text-[10px] px-1 rounded bg-amber-600/30 text-amber-300", "custom" }
+                    }
+                }
+                if entry.versions.len() > 1 {
+                    div { class: "text-xs text-subtle", "{entry.versions.len()} versions" }
+                } else if let Some(version) = &info.version {
+                    div { class: "text-xs text-subtle", "Version: {version}" }
+                }
+            }
+            span { class: "text-xs text-muted whitespace-nowrap", "Lvl {info.combat_level}" }
+        }
+    }
+}
+
+fn get_monster_key(entry: &MonsterEntry) -> String {
+    entry.monster.key()
+}
+
+#[component]
+pub fn MonsterSelect() -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+
+    if MONSTERS.is_empty() {
+        return rsx! {
+            div { class: "panel p-4 text-error",
+                "Error: Could not parse embedded monster data. Check console for details."
+            }
+        };
+    }
+
+    // Custom monsters are listed first so they aren't buried under wiki
+    // entries. Versions sharing a name are grouped under the first one seen.
+    let mut entries: Vec<MonsterEntry> = Vec::new();
+    let mut by_name: HashMap<(String, bool), usize> = HashMap::new();
+    let custom_monsters = app_state.read().custom_monsters.clone();
+    let monsters = custom_monsters
+        .iter()
+        .map(|monster| (monster, true))
+        .chain(MONSTERS.iter().map(|monster| (monster, false)));
+    for (monster, is_custom) in monsters {
+        let version = monster.info.version.clone().unwrap_or_default();
+        match by_name.entry((monster.info.name.clone(), is_custom)) {
+            Entry::Occupied(idx) => entries[*idx.get()].versions.push(version),
+            Entry::Vacant(slot) => {
+                slot.insert(entries.len());
+                entries.push(MonsterEntry {
+                    monster: monster.clone(),
+                    versions: vec![version],
+                    is_custom,
+                });
+            }
+        }
+    }
+
+    rsx! {
+        SearchBar {
+            items: entries,
+            filter_fn: filter_monster,
+            render_item: render_monster_item,
+            get_key: get_monster_key,
+            on_select: move |entry: MonsterEntry| {
+                app_state.write().monster = Some(SelectedMonster::new(entry.monster));
+            },
+            placeholder: "Search for a monster...".to_string(),
+        }
+    }
+}
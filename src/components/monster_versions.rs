@@ -0,0 +1,139 @@
+use crate::monster::{versions_of, MonsterJson, SelectedMonster};
+use crate::state::AppState;
+use dioxus::prelude::*;
+use std::collections::HashMap;
+
+/// Whether a version is part of the whole fight unless the user says
+/// otherwise: the other numbered phases of the selected mode, or just the
+/// selected version for monsters without phases.
+fn in_fight_by_default(version: &MonsterJson, selected: &MonsterJson) -> bool {
+    match (version.phase(), selected.phase()) {
+        (Some((mode, _)), Some((selected_mode, _))) => mode == selected_mode,
+        _ => version.same_entry(selected),
+    }
+}
+
+fn version_label(version: &MonsterJson) -> String {
+    version
+        .info
+        .version
+        .clone()
+        .unwrap_or_else(|| "Default".to_string())
+}
+
+/// Switches between versions of the selected monster, and adds up the time to
+/// kill across the phases of a multi-phase fight.
+#[component]
+pub fn MonsterVersions() -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+    let mut whole_fight = use_signal(|| false);
+    // Versions the user ticked or unticked, by monster key
+    let mut in_fight = use_signal(HashMap::<String, bool>::new);
+
+    let (selected, versions) = {
+        let state = app_state.read();
+        let Some(monster) = state.monster.as_ref() else {
+            return rsx! {};
+        };
+        let versions = versions_of(&monster.wiki.info.name, &state.custom_monsters);
+        (monster.clone(), versions)
+    };
+    if versions.len() < 2 {
+        return rsx! {};
+    }
+
+    let included: Vec<bool> = versions
+        .iter()
+        .map(|version| {
+            in_fight
+                .read()
+                .get(&version.key())
+                .copied()
+                .unwrap_or_else(|| in_fight_by_default(version, &selected.wiki))
+        })
+        .collect();
+
+    // Seconds to kill each included version, keeping any edits to the
+    // selected one, and the whole fight if every phase can be damaged
+    type Row = (String, String, bool, Option<f64>);
+    let fight: Option<(Vec<Row>, Option<f64>)> = whole_fight().then(|| {
+        let state = app_state.read();
+        let per_version: Vec<Row> = versions
+            .iter()
+            .zip(&included)
+            .map(|(version, &included)| {
+                let phase = state.scaled(if version.same_entry(&selected.wiki) {
+                    selected.clone()
+                } else {
+                    SelectedMonster::new(version.clone())
+                });
+                let ttk = included
+                    .then(|| state.fight_stats(&phase).ttk_secs)
+                    .flatten();
+                (version.key(), version_label(version), included, ttk)
+            })
+            .collect();
+        let total = per_version
+            .iter()
+            .filter(|(_, _, included, _)| *included)
+            .map(|(_, _, _, ttk)| *ttk)
+            .sum();
+        (per_version, total)
+    });
+    let format_ttk = |ttk: Option<f64>| ttk.map_or("–".to_string(), |secs| format!("{secs:.1}s"));
+
+    rsx! {
+        div { class: "panel-elevated p-3 mt-2 text-sm",
+            div { class: "flex items-center justify-between mb-2",
+                h4 { class: "text-xs font-semibold text-muted", "Versions" }
+                label { class: "flex items-center gap-1 text-xs text-muted cursor-pointer",
+                    input {
+                        "type": "checkbox",
+                        checked: whole_fight(),
+                        onchange: move |evt| whole_fight.set(evt.checked()),
+                    }
+                    "Whole fight"
+                }
+            }
+            div { class: "flex flex-wrap gap-1",
+                for (version, label, is_selected) in versions.iter().map(|version| {
+                    (version.clone(), version_label(version), version.same_entry(&selected.wiki))
+                }) {
+                    button {
+                        key: "{label}",
+                        "type": "button",
+                        class: "text-xs px-2 py-1 rounded hover:bg-gray-700",
+                        class: if is_selected { "bg-gray-700 text-accent" } else { "bg-gray-800/50" },
+                        onclick: move |_| {
+                            app_state.write().monster = Some(SelectedMonster::new(version.clone()));
+                        },
+                        "{label}"
+                    }
+                }
+            }
+            if let Some((per_version, total)) = fight {
+                div { class: "grid grid-cols-[1fr_auto] gap-x-4 gap-y-1 mt-2 text-xs",
+                    for (key, label, included, ttk) in per_version {
+                        label {
+                            key: "{key}",
+                            class: "flex items-center gap-2 cursor-pointer",
+                            input {
+                                "type": "checkbox",
+                                checked: included,
+                                onchange: move |evt| {
+                                    in_fight.write().insert(key.clone(), evt.checked());
+                                },
+                            }
+                            "{label}"
+                        }
+                        span { class: "text-right text-muted",
+                            if included { {format_ttk(ttk)} }
+                        }
+                    }
+                    span { class: "font-semibold", "Whole fight" }
+                    span { class: "text-right font-bold", {format_ttk(total)} }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::hiscores::normalize_stats_text;
+use dioxus::prelude::*;
+
+#[component]
+pub fn PasteStatsForm(disabled: bool, on_import: EventHandler<String>) -> Element {
+    let mut is_open = use_signal(|| false);
+    let mut text = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+
+    rsx! {
+        div { class: "mt-2",
+            if !is_open() {
+                button {
+                    "type": "button",
+                    class: "btn-secondary text-sm w-full py-1",
+                    disabled,
+                    onclick: move |_| is_open.set(true),
+                    "Paste stats"
+                }
+            } else {
+                div { class: "panel-elevated p-2 flex flex-col gap-2 text-sm",
+                    span { class: "text-xs text-muted",
+                        "Paste an index_lite response, hiscore JSON, or a RuneLite skill export."
+                    }
+                    textarea {
+                        class: "input w-full h-32 text-xs font-mono",
+                        placeholder: "Attack: 99\nStrength: 13,034,431\n...",
+                        value: "{text}",
+                        oninput: move |evt| text.set(evt.value()),
+                    }
+
+                    if let Some(message) = error.read().as_ref() {
+                        div { class: "text-xs text-red-300", "{message}" }
+                    }
+
+                    div { class: "flex gap-2",
+                        button {
+                            "type": "button",
+                            class: "btn-primary text-sm flex-grow py-1",
+                            disabled: disabled || text.read().trim().is_empty(),
+                            onclick: move |_| {
+                                let result = normalize_stats_text(&text.read());
+                                match result {
+                                    Ok(data) => {
+                                        on_import.call(data);
+                                        text.set(String::new());
+                                        error.set(None);
+                                        is_open.set(false);
+                                    }
+                                    Err(e) => error.set(Some(e.to_string())),
+                                }
+                            },
+                            "Import"
+                        }
+                        button {
+                            "type": "button",
+                            class: "btn-secondary text-sm py-1 px-3",
+                            onclick: move |_| {
+                                error.set(None);
+                                is_open.set(false);
+                            },
+                            "Cancel"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
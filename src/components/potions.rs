@@ -1,3 +1,5 @@
+use crate::components::boost_decay::BoostDecayPanel;
+use crate::components::custom_boost::{CustomBoostForm, CustomBoostSlot};
 use crate::components::search_bar::SearchBar;
 use crate::state::AppState;
 use crate::POTIONS_ASSETS;
@@ -7,49 +9,6 @@ use strum::IntoEnumIterator;
 
 const MAX_ACTIVE_POTIONS: usize = 4;
 
-fn get_active_potions_from_state(app_state: &AppState) -> Vec<Potion> {
-    let mut active_potions = Vec::new();
-    let potions = &app_state.player.potions;
-
-    if let Some(attack_potions) = &potions.attack {
-        for boost in attack_potions {
-            if !active_potions.contains(&boost.potion_type) {
-                active_potions.push(boost.potion_type);
-            }
-        }
-    }
-    if let Some(strength_potions) = &potions.strength {
-        for boost in strength_potions {
-            if !active_potions.contains(&boost.potion_type) {
-                active_potions.push(boost.potion_type);
-            }
-        }
-    }
-    if let Some(defence_potions) = &potions.defence {
-        for boost in defence_potions {
-            if !active_potions.contains(&boost.potion_type) {
-                active_potions.push(boost.potion_type);
-            }
-        }
-    }
-    if let Some(ranged_potions) = &potions.ranged {
-        for boost in ranged_potions {
-            if !active_potions.contains(&boost.potion_type) {
-                active_potions.push(boost.potion_type);
-            }
-        }
-    }
-    if let Some(magic_potions) = &potions.magic {
-        for boost in magic_potions {
-            if !active_potions.contains(&boost.potion_type) {
-                active_potions.push(boost.potion_type);
-            }
-        }
-    }
-
-    active_potions
-}
-
 fn filter_potion(potion: &Potion, term: &str) -> bool {
     potion.to_string().to_lowercase().contains(term)
 }
@@ -87,10 +46,7 @@ pub fn PotionSelect() -> Element {
     let mut is_collapsed = use_signal(|| false);
 
     // Derive active potions from app_state (single source of truth)
-    let active_potions = use_memo(move || {
-        let state = app_state.read();
-        get_active_potions_from_state(&state)
-    });
+    let active_potions = use_memo(move || app_state.read().active_potions());
 
     // Compute available potions (all potions minus active ones)
     let available_potions: Vec<Potion> = {
@@ -100,7 +56,13 @@ pub fn PotionSelect() -> Element {
             .collect()
     };
 
-    let is_at_max = active_potions.read().len() >= MAX_ACTIVE_POTIONS;
+    let custom_boosts = app_state.read().custom_boosts.clone();
+    let used_slots = active_potions.read().len() + custom_boosts.len();
+    let is_at_max = used_slots >= MAX_ACTIVE_POTIONS;
+    let has_heart = active_potions
+        .read()
+        .iter()
+        .any(|potion| matches!(potion, Potion::ImbuedHeart | Potion::SaturatedHeart));
 
     rsx! {
         div {
@@ -114,7 +76,7 @@ pub fn PotionSelect() -> Element {
                         class: "text-sm font-semibold text-accent w-12",
                         "Boosts"
                     }
-                    if !active_potions.read().is_empty() && is_collapsed() {
+                    if used_slots > 0 && is_collapsed() {
                         div {
                             class: "flex gap-2",
                             for potion in active_potions.read().iter() {
@@ -125,6 +87,14 @@ pub fn PotionSelect() -> Element {
                                     title: "{potion}"
                                 }
                             }
+                            for boost in custom_boosts.iter() {
+                                img {
+                                    class: "w-5 h-5 object-contain",
+                                    src: "{boost.skill.icon_path()}",
+                                    alt: "{boost.skill.name()}",
+                                    title: "Custom boost: {boost.label()} {boost.skill.name()}"
+                                }
+                            }
                         }
                     }
                 }
@@ -146,12 +116,27 @@ pub fn PotionSelect() -> Element {
                                 key: "active-potion-{idx}",
                                 potion: *potion,
                                 on_remove: move |potion: Potion| {
-                                    app_state.write().player.remove_potion(potion);
+                                    let mut state = app_state.write();
+                                    state.player.remove_potion(potion);
+                                    state.refresh_current_stats();
+                                }
+                            }
+                        }
+                        for (idx, boost) in custom_boosts.iter().enumerate() {
+                            CustomBoostSlot {
+                                key: "custom-boost-{idx}",
+                                boost: *boost,
+                                on_remove: move |_| {
+                                    let mut state = app_state.write();
+                                    if idx < state.custom_boosts.len() {
+                                        state.custom_boosts.remove(idx);
+                                    }
+                                    state.refresh_current_stats();
                                 }
                             }
                         }
                         // Empty slots
-                        for idx in active_potions.read().len()..MAX_ACTIVE_POTIONS {
+                        for idx in used_slots..MAX_ACTIVE_POTIONS {
                             EmptyPotionSlot { key: "empty-potion-{idx}" }
                         }
                     }
@@ -164,8 +149,10 @@ pub fn PotionSelect() -> Element {
                             render_item: render_potion_item,
                             get_key: get_potion_key,
                             on_select: move |potion: Potion| {
-                                if active_potions.read().len() < MAX_ACTIVE_POTIONS {
-                                    app_state.write().player.add_potion(potion);
+                                if !is_at_max {
+                                    let mut state = app_state.write();
+                                    state.player.add_potion(potion);
+                                    state.refresh_current_stats();
                                 }
                             },
                             placeholder: "Search for boosts...".to_string(),
@@ -173,6 +160,18 @@ pub fn PotionSelect() -> Element {
                             disabled: is_at_max,
                         }
                     }
+
+                    CustomBoostForm {
+                        disabled: is_at_max,
+                        on_add: move |boost| {
+                            let mut state = app_state.write();
+                            state.custom_boosts.push(boost);
+                            state.refresh_current_stats();
+                        }
+                    }
+
+                    // Boost decay over the fight
+                    BoostDecayPanel { has_heart }
                 }
             }
         }
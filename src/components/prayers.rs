@@ -1,7 +1,9 @@
 use crate::state::AppState;
+use crate::unlocks::Unlock;
 use crate::PRAYERS_ASSETS;
 use dioxus::prelude::*;
 use osrs::types::prayers::Prayer;
+use strum::IntoEnumIterator;
 
 const PRAYER_ROWS: [[Prayer; 5]; 5] = [
     [
@@ -49,6 +51,10 @@ pub fn PrayerSelect() -> Element {
     // Read state once and collect active prayers
     let player_prayers = state.read().player.prayers.clone();
     let is_prayer_active = |prayer: Prayer| player_prayers.contains_prayer(prayer);
+    let is_prayer_locked = |prayer: Prayer| {
+        let state = state.read();
+        Unlock::iter().any(|unlock| unlock.prayer() == Some(prayer) && !state.is_unlocked(unlock))
+    };
 
     rsx! {
         div {
@@ -103,6 +109,7 @@ pub fn PrayerSelect() -> Element {
                                         key: "prayer-{row_idx}-{col_idx}",
                                         prayer: *prayer,
                                         is_active: is_prayer_active(*prayer),
+                                        is_locked: is_prayer_locked(*prayer),
                                         on_click: move |prayer: Prayer| {
                                             let mut app_state = state.write();
                                             if app_state.player.prayers.contains_prayer(prayer) {
@@ -123,8 +130,18 @@ pub fn PrayerSelect() -> Element {
 }
 
 #[component]
-fn PrayerButton(prayer: Prayer, is_active: bool, on_click: EventHandler<Prayer>) -> Element {
+fn PrayerButton(
+    prayer: Prayer,
+    is_active: bool,
+    is_locked: bool,
+    on_click: EventHandler<Prayer>,
+) -> Element {
     let img_path = get_prayer_img_path(prayer);
+    let title = if is_locked {
+        format!("{prayer} (not unlocked)")
+    } else {
+        prayer.to_string()
+    };
     let button_class = if is_active {
         "relative w-8 h-8 bg-gray-700 rounded-full cursor-pointer transition-all duration-150 hover:scale-105 flex items-center justify-center"
     } else {
@@ -134,7 +151,7 @@ fn PrayerButton(prayer: Prayer, is_active: bool, on_click: EventHandler<Prayer>)
     rsx! {
         div {
             class: "{button_class}",
-            title: "{prayer}",
+            title: "{title}",
             onclick: move |_| on_click.call(prayer),
             img {
                 class: "p-1 object-contain",
@@ -148,6 +165,11 @@ fn PrayerButton(prayer: Prayer, is_active: bool, on_click: EventHandler<Prayer>)
                     alt: "Selected",
                 }
             }
+            if is_locked {
+                span { class: "absolute -bottom-1 -right-1 text-[10px] leading-none pointer-events-none",
+                    "🔒"
+                }
+            }
         }
     }
 }
@@ -0,0 +1,85 @@
+use crate::state::AppState;
+use crate::storage::format_date;
+use dioxus::prelude::*;
+
+#[component]
+pub fn ProfileSelect(disabled: bool) -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+    let mut load_error = use_signal(|| None::<String>);
+
+    let mut load = move |profile: usize, snapshot: usize| {
+        let result = app_state.write().load_snapshot(profile, snapshot);
+        load_error.set(result.err().map(|e| e.to_string()));
+    };
+
+    let store = app_state.read().profiles.clone();
+    if store.profiles.is_empty() {
+        return rsx! {};
+    }
+    let active = store.active;
+    let loaded_snapshot = store.loaded_snapshot;
+
+    rsx! {
+        div { class: "mt-2 flex flex-col gap-2 text-sm",
+            span { class: "text-xs text-subtle", "Saved accounts" }
+            div { class: "flex flex-wrap gap-1",
+                for (idx, profile) in store.profiles.iter().enumerate() {
+                    div {
+                        key: "{profile.name}",
+                        class: "relative group",
+                        button {
+                            "type": "button",
+                            class: if active == Some(idx) { "btn-primary text-xs py-1 px-2" } else { "btn-secondary text-xs py-1 px-2" },
+                            title: profile.mode.map(|mode| mode.name()).unwrap_or_default(),
+                            disabled,
+                            onclick: {
+                                let latest = profile.snapshots.len().saturating_sub(1);
+                                move |_| load(idx, latest)
+                            },
+                            "{profile.name}"
+                        }
+                        button {
+                            "type": "button",
+                            class: "absolute -top-1 -right-1 w-4 h-4 bg-red-600 hover:bg-red-700 rounded-full flex items-center justify-center opacity-0 group-hover:opacity-100 transition-opacity duration-150",
+                            style: "font-size: 10px; line-height: 1; font-family: monospace;",
+                            title: "Forget this account",
+                            onclick: move |_| app_state.write().profiles.remove(idx),
+                            "×"
+                        }
+                    }
+                }
+            }
+
+            // Earlier stats of the active account, newest first
+            if let Some(profile) = store.active_profile().filter(|p| p.snapshots.len() > 1) {
+                select {
+                    class: "input w-full h-8 py-0 px-2 text-sm",
+                    disabled,
+                    onchange: move |evt| {
+                        if let (Some(profile), Ok(snapshot)) = (active, evt.value().parse::<usize>()) {
+                            load(profile, snapshot);
+                        }
+                    },
+                    for (idx, snapshot) in profile.snapshots.iter().enumerate().rev() {
+                        option {
+                            value: "{idx}",
+                            selected: loaded_snapshot == Some(idx),
+                            if idx + 1 == profile.snapshots.len() {
+                                "{format_date(snapshot.taken_at)} (latest)"
+                            } else {
+                                "{format_date(snapshot.taken_at)}"
+                            }
+                        }
+                    }
+                    if loaded_snapshot.is_none() {
+                        option { value: "", selected: true, disabled: true, "Edited stats" }
+                    }
+                }
+            }
+
+            if let Some(message) = load_error.read().as_ref() {
+                div { class: "text-xs text-red-300", "{message}" }
+            }
+        }
+    }
+}
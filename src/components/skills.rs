@@ -1,123 +1,131 @@
+use crate::boosts::StatModifier;
+use crate::components::activities::ActivityScores;
+use crate::components::paste_stats::PasteStatsForm;
+use crate::components::profiles::ProfileSelect;
+use crate::components::training_planner::TrainingPlanner;
+use crate::hiscores::{fetch_via_proxy, GameMode, HiscoreError, HiscoreLookup, FALLBACK_ORDER};
+use crate::skill::{
+    combat_level, format_xp, next_combat_level, parse_level_input, LevelInput, Skill,
+    COMBAT_SKILLS, SKILLS_TAB_LAYOUT,
+};
 use crate::state::AppState;
-use crate::BONUSES_ASSETS;
+use crate::storage::now_secs;
+use crate::timers;
 use dioxus::prelude::*;
-use osrs::types::player::parse_player_data;
-
-// Define skill types and order
-#[derive(Clone, Copy, Debug, PartialEq)]
-enum Skill {
-    Attack,
-    Strength,
-    Defence,
-    Ranged,
-    Magic,
-    Hitpoints,
-    Prayer,
-    Mining,
-    Herblore,
-}
+use std::time::Duration;
+use strum::IntoEnumIterator;
 
-impl Skill {
-    fn name(&self) -> &'static str {
-        match self {
-            Skill::Attack => "Attack",
-            Skill::Strength => "Strength",
-            Skill::Defence => "Defence",
-            Skill::Ranged => "Ranged",
-            Skill::Magic => "Magic",
-            Skill::Hitpoints => "Hitpoints",
-            Skill::Prayer => "Prayer",
-            Skill::Mining => "Mining",
-            Skill::Herblore => "Herblore",
-        }
-    }
+// Value of the mode selector option that searches every hiscore table
+const ALL_MODES_OPTION: &str = "Try all modes";
 
-    fn icon_path(&self) -> String {
-        format!("{BONUSES_ASSETS}/{}.png", self.name().to_lowercase())
-    }
+#[server]
+async fn fetch_player_data_server(
+    rsn: String,
+    mode: GameMode,
+) -> Result<Result<HiscoreLookup, HiscoreError>, dioxus::prelude::ServerFnError> {
+    Ok(crate::hiscore_cache::fetch(&rsn, mode).await)
 }
 
-const ALL_SKILLS: [Skill; 9] = [
-    Skill::Attack,
-    Skill::Strength,
-    Skill::Defence,
-    Skill::Ranged,
-    Skill::Magic,
-    Skill::Hitpoints,
-    Skill::Prayer,
-    Skill::Mining,
-    Skill::Herblore,
-];
-
-const COMBAT_SKILLS: [Skill; 6] = [
-    Skill::Attack,
-    Skill::Strength,
-    Skill::Defence,
-    Skill::Ranged,
-    Skill::Magic,
-    Skill::Hitpoints,
-];
-
-#[server]
-async fn fetch_player_data_server(rsn: String) -> Result<String, dioxus::prelude::ServerFnError> {
-    let url = "https://secure.runescape.com/m=hiscore_oldschool/index_lite.ws";
-    let params = [("player", rsn.as_str())];
-    let client = reqwest::Client::new();
-    let response = client
-        .get(url)
-        .query(&params)
-        .send()
-        .await
-        .map_err(|e| dioxus::prelude::ServerFnError::new(e.to_string()))?;
-    let data = response
-        .text()
+// Fetches through the CORS proxy when one is set, otherwise through the app
+// server, flattening a failed server call into the hiscore error type
+async fn fetch_player_data(
+    rsn: &str,
+    mode: GameMode,
+    proxy: Option<&str>,
+) -> Result<HiscoreLookup, HiscoreError> {
+    if let Some(proxy) = proxy {
+        let data = fetch_via_proxy(proxy, rsn, mode).await?;
+        return Ok(HiscoreLookup { data, age_secs: 0 });
+    }
+    fetch_player_data_server(rsn.to_string(), mode)
         .await
-        .map_err(|e| dioxus::prelude::ServerFnError::new(e.to_string()))?;
-    Ok(data)
+        .unwrap_or_else(|e| Err(HiscoreError::Unavailable(e.to_string())))
 }
 
+/// Looks the player up on the given hiscore table, or on each table in turn
+/// when no mode is given. Returns the mode the player was found in and the
+/// age of the data in seconds.
 async fn lookup_stats(
     app_state: &mut Signal<AppState>,
     rsn: &str,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let stats_data = fetch_player_data_server(rsn.to_string()).await?;
+    mode: Option<GameMode>,
+) -> Result<(GameMode, u64), HiscoreError> {
+    let proxy = app_state.read().hiscore_proxy.clone();
+    let proxy = proxy.as_deref();
+    let (lookup, found_mode) = match mode {
+        Some(mode) => (fetch_player_data(rsn, mode, proxy).await?, mode),
+        None => {
+            let mut found = None;
+            for mode in FALLBACK_ORDER {
+                match fetch_player_data(rsn, mode, proxy).await {
+                    Ok(lookup) => {
+                        found = Some((lookup, mode));
+                        break;
+                    }
+                    Err(HiscoreError::NotFound) => continue,
+                    Err(e) => return Err(e),
+                }
+            }
+            found.ok_or(HiscoreError::NotFound)?
+        }
+    };
     let mut state = app_state.write();
-    state.player.stats = parse_player_data(stats_data)?;
-    state.player.attrs.name = Some(rsn.to_string());
-    Ok(())
+    state.apply_hiscore_data(lookup.data.clone(), Some(rsn.to_string()))?;
+    let taken_at = now_secs().saturating_sub(lookup.age_secs);
+    state.record_profile(rsn, Some(found_mode), &lookup.data, taken_at);
+    Ok((found_mode, lookup.age_secs))
 }
 
 #[component]
 pub fn SkillsSelect() -> Element {
-    let app_state = use_context::<Signal<AppState>>();
+    let mut app_state = use_context::<Signal<AppState>>();
     let mut is_collapsed = use_signal(|| false);
     let mut rsn_input = use_signal(String::new);
     let mut is_loading = use_signal(|| false);
-    let mut error_message = use_signal(|| None::<String>);
+    let mut lookup_error = use_signal(|| None::<HiscoreError>);
+    let mut selected_mode = use_signal(|| Some(GameMode::Regular));
+    let mut found_message = use_signal(|| None::<String>);
+    // Age of the last lookup's data when it arrived, plus minutes since then
+    let mut data_age_minutes = use_signal(|| None::<u64>);
+    let mut minutes_since_lookup = use_signal(|| 0u64);
+
+    use_future(move || async move {
+        loop {
+            timers::sleep(Duration::from_secs(60)).await;
+            minutes_since_lookup += 1;
+        }
+    });
 
     let mut perform_lookup = move || {
         let rsn = rsn_input.read().trim().to_string();
         if !rsn.is_empty() {
             is_loading.set(true);
             let mut state_signal = app_state;
+            let mode = selected_mode();
             spawn(async move {
                 // Clear any previous error
-                error_message.set(None);
+                lookup_error.set(None);
+                found_message.set(None);
 
                 // Use our web-compatible lookup function
-                let result = {
-                    // let mut state = state_signal.write();
-                    lookup_stats(&mut state_signal, &rsn).await
-                };
+                let result = lookup_stats(&mut state_signal, &rsn, mode).await;
 
                 match result {
-                    Ok(()) => {
+                    Ok((found_mode, age_secs)) => {
                         // Success - stats were updated
                         rsn_input.set(String::new()); // Clear the input on success
+                        data_age_minutes.set(Some(age_secs / 60));
+                        minutes_since_lookup.set(0);
+                        if mode.is_none() {
+                            found_message.set(Some(format!(
+                                "Found '{rsn}' on the {} hiscores",
+                                found_mode.name()
+                            )));
+                        }
                     }
                     Err(e) => {
                         // Handle the error gracefully
-                        error_message.set(Some(format!("Failed to lookup stats: {e}")));
+                        lookup_error.set(Some(e));
                     }
                 }
 
@@ -129,6 +137,28 @@ pub fn SkillsSelect() -> Element {
 
     let lookup_stats = move |_| perform_lookup();
 
+    // Pasted stats are named after the RSN field when it is filled in
+    let import_stats = move |data: String| {
+        let rsn = rsn_input.read().trim().to_string();
+        let name = (!rsn.is_empty()).then_some(rsn);
+        lookup_error.set(None);
+        let mut state = app_state.write();
+        match state.apply_hiscore_data(data.clone(), name.clone()) {
+            Ok(()) => {
+                match name {
+                    Some(name) => state.record_profile(&name, None, &data, now_secs()),
+                    None => state.profiles.loaded_snapshot = None,
+                }
+                rsn_input.set(String::new());
+                data_age_minutes.set(None);
+                found_message.set(Some("Imported pasted stats".to_string()));
+            }
+            Err(e) => lookup_error.set(Some(e)),
+        }
+    };
+
+    let proxy_value = app_state.read().hiscore_proxy.clone().unwrap_or_default();
+
     rsx! {
         div { class: "min-w-60",
             // Toggle header
@@ -196,20 +226,84 @@ pub fn SkillsSelect() -> Element {
                             }
                         }
 
+                        select {
+                            class: "input w-full h-8 py-0 px-2 text-sm mt-2",
+                            disabled: is_loading(),
+                            onchange: move |evt| selected_mode.set(GameMode::from_name(&evt.value())),
+                            for mode in GameMode::iter() {
+                                option {
+                                    value: "{mode.name()}",
+                                    selected: selected_mode() == Some(mode),
+                                    "{mode.name()}"
+                                }
+                            }
+                            option {
+                                value: ALL_MODES_OPTION,
+                                selected: selected_mode().is_none(),
+                                "{ALL_MODES_OPTION}"
+                            }
+                        }
+
+                        input {
+                            "type": "url",
+                            class: "input w-full h-8 text-sm mt-2",
+                            placeholder: "CORS proxy URL (optional)",
+                            title: "Look up hiscores from the browser through this proxy, for builds hosted without the app server. The encoded hiscore URL is appended to it.",
+                            value: "{proxy_value}",
+                            disabled: is_loading(),
+                            oninput: move |evt| {
+                                let proxy = evt.value().trim().to_string();
+                                app_state.write().hiscore_proxy = (!proxy.is_empty()).then_some(proxy);
+                            },
+                        }
+
+                        PasteStatsForm { disabled: is_loading(), on_import: import_stats }
+                        ProfileSelect { disabled: is_loading() }
+
+                        if let Some(message) = found_message.read().as_ref() {
+                            div { class: "mt-2 text-xs text-success", "{message}" }
+                        }
+
+                        if let Some(age) = data_age_minutes() {
+                            div { class: "mt-1 text-xs text-subtle",
+                                {
+                                    match age + minutes_since_lookup() {
+                                        0 => "Updated just now".to_string(),
+                                        1 => "Updated 1 minute ago".to_string(),
+                                        minutes => format!("Updated {minutes} minutes ago"),
+                                    }
+                                }
+                            }
+                        }
+
                         // Error message display
-                        if let Some(error) = error_message.read().as_ref() {
-                            div { class: "mt-2 p-2 bg-red-600/20 border border-red-600/30 rounded text-red-300 text-sm",
-                                "{error}"
+                        if let Some(error) = lookup_error.read().as_ref() {
+                            div { class: "mt-2 p-2 bg-red-600/20 border border-red-600/30 rounded text-red-300 text-sm flex items-center justify-between gap-2",
+                                span { "{error}" }
+                                if error.is_retryable() {
+                                    button {
+                                        "type": "button",
+                                        class: "btn-secondary text-xs py-1 px-2",
+                                        disabled: is_loading() || rsn_input.read().trim().is_empty(),
+                                        onclick: move |_| perform_lookup(),
+                                        "Retry"
+                                    }
+                                }
                             }
                         }
                     }
 
-                    // All skills grid
-                    div { class: "grid grid-cols-2 gap-1 mx-auto w-fit",
-                        for skill in ALL_SKILLS.iter() {
+                    // All skills in the in-game skills tab layout
+                    div { class: "grid grid-cols-3 gap-1 mx-auto w-fit",
+                        for skill in SKILLS_TAB_LAYOUT.iter() {
                             SkillDisplay { skill: *skill }
                         }
+                        TotalLevelDisplay {}
                     }
+
+                    CombatLevelDisplay {}
+                    TrainingPlanner {}
+                    ActivityScores {}
                 }
             }
         }
@@ -220,7 +314,7 @@ pub fn SkillsSelect() -> Element {
 fn SkillIconDisplay(skill: Skill) -> Element {
     let app_state = use_context::<Signal<AppState>>();
 
-    let (_base_level, current_level) = get_skill_levels(&app_state.read(), skill);
+    let (base_level, current_level) = app_state.read().skill_levels(skill);
 
     rsx! {
         div { class: "flex items-center gap-1",
@@ -230,7 +324,7 @@ fn SkillIconDisplay(skill: Skill) -> Element {
                 alt: "{skill.name()}",
                 title: "{skill.name()}",
             }
-            span { class: "text-xs font-medium", "{current_level}" }
+            span { class: "text-xs font-medium {level_colour(base_level, current_level)}", "{current_level}" }
         }
     }
 }
@@ -239,7 +333,14 @@ fn SkillIconDisplay(skill: Skill) -> Element {
 fn SkillDisplay(skill: Skill) -> Element {
     let mut app_state = use_context::<Signal<AppState>>();
 
-    let (base_level, current_level) = get_skill_levels(&app_state.read(), skill);
+    let (base_level, current_level) = app_state.read().skill_levels(skill);
+    let modifiers = app_state.read().stat_modifiers(skill);
+    let breakdown = modifier_breakdown(skill, base_level, current_level, &modifiers);
+    let level_class = level_colour(base_level, current_level);
+    let predicted_level = app_state.read().predicted_level(skill);
+    let is_overridden = app_state.read().current_overrides.contains_key(&skill);
+    let xp = app_state.read().xp(skill);
+    let input_class = "input w-12 h-5 text-center text-sm px-1 py-0 [appearance:textfield] [&::-webkit-outer-spin-button]:appearance-none [&::-webkit-inner-spin-button]:appearance-none";
 
     rsx! {
         div { class: "flex items-center justify-center gap-1 p-1 rounded bg-gray-800/50",
@@ -250,80 +351,122 @@ fn SkillDisplay(skill: Skill) -> Element {
                 title: "{skill.name()}",
             }
             div { class: "flex items-center gap-1 text-sm min-w-0",
-                span { class: "font-bold", "{current_level}" }
-                span { "/" }
+                if skill.is_player_stat() {
+                    input {
+                        "type": "number",
+                        class: "{input_class} font-bold {level_class}",
+                        class: if is_overridden { "border-amber-500" } else { "" },
+                        min: "0",
+                        title: "{breakdown}",
+                        value: "{current_level}",
+                        oninput: move |evt| {
+                            if let Ok(new_level) = evt.value().parse::<u32>() {
+                                app_state.write().override_current_level(skill, new_level);
+                            }
+                        },
+                    }
+                    span { "/" }
+                }
                 input {
-                    "type": "number",
-                    class: "input w-12 h-5 text-center text-sm px-1 py-0 [appearance:textfield] [&::-webkit-outer-spin-button]:appearance-none [&::-webkit-inner-spin-button]:appearance-none",
-                    min: "0",
-                    max: "99",
+                    "type": "text",
+                    class: "{input_class}",
+                    title: "{format_xp(xp)} xp. Enter a level or an XP value (e.g. 1.2m).",
                     value: "{base_level}",
-                    oninput: move |evt| {
-                        if let Ok(new_level) = evt.value().parse::<u8>() {
-                            if new_level <= 99 {
-                                set_skill_base_level(&mut app_state.write(), skill, new_level as u32);
-                            }
+                    onchange: move |evt| match parse_level_input(&evt.value()) {
+                        Some(LevelInput::Level(level)) => {
+                            app_state.write().set_base_level(skill, level.max(skill.min_level()));
                         }
+                        Some(LevelInput::Xp(xp)) => app_state.write().set_xp(skill, xp),
+                        None => {}
                     },
                 }
+                if is_overridden {
+                    button {
+                        "type": "button",
+                        class: "text-warning text-xs leading-none",
+                        title: "Manually set (potions predict {predicted_level}). Click to reset.",
+                        onclick: move |_| {
+                            let mut state = app_state.write();
+                            state.current_overrides.remove(&skill);
+                            state.refresh_current_stats();
+                        },
+                        "↺"
+                    }
+                }
             }
         }
     }
 }
 
-// Helper functions to get and set skill levels
-fn get_skill_levels(app_state: &AppState, skill: Skill) -> (u32, u32) {
-    match skill {
-        Skill::Attack => (
-            app_state.player.stats.attack.base,
-            app_state.player.stats.attack.current,
-        ),
-        Skill::Strength => (
-            app_state.player.stats.strength.base,
-            app_state.player.stats.strength.current,
-        ),
-        Skill::Defence => (
-            app_state.player.stats.defence.base,
-            app_state.player.stats.defence.current,
-        ),
-        Skill::Ranged => (
-            app_state.player.stats.ranged.base,
-            app_state.player.stats.ranged.current,
-        ),
-        Skill::Magic => (
-            app_state.player.stats.magic.base,
-            app_state.player.stats.magic.current,
-        ),
-        Skill::Hitpoints => (
-            app_state.player.stats.hitpoints.base,
-            app_state.player.stats.hitpoints.current,
-        ),
-        Skill::Prayer => (
-            app_state.player.stats.prayer.base,
-            app_state.player.stats.prayer.current,
-        ),
-        Skill::Mining => (
-            app_state.player.stats.mining.base,
-            app_state.player.stats.mining.current,
-        ),
-        Skill::Herblore => (
-            app_state.player.stats.herblore.base,
-            app_state.player.stats.herblore.current,
-        ),
+fn level_colour(base_level: u32, current_level: u32) -> &'static str {
+    match current_level.cmp(&base_level) {
+        std::cmp::Ordering::Greater => "text-success",
+        std::cmp::Ordering::Less => "text-red-400",
+        std::cmp::Ordering::Equal => "",
+    }
+}
+
+// Tooltip text listing every potion or boost that moved the stat
+fn modifier_breakdown(
+    skill: Skill,
+    base_level: u32,
+    current_level: u32,
+    modifiers: &[StatModifier],
+) -> String {
+    let mut lines = vec![format!("{}: {base_level}", skill.name())];
+    for modifier in modifiers {
+        let note = if modifier.applied {
+            ""
+        } else {
+            " (overlapped)"
+        };
+        lines.push(format!("{}: {:+}{note}", modifier.source, modifier.amount));
     }
+    lines.push(format!("Current: {current_level}"));
+    lines.push("Edit to set the current level by hand".to_string());
+    lines.join("\n")
 }
 
-fn set_skill_base_level(app_state: &mut AppState, skill: Skill, level: u32) {
-    match skill {
-        Skill::Attack => app_state.player.stats.attack.base = level,
-        Skill::Strength => app_state.player.stats.strength.base = level,
-        Skill::Defence => app_state.player.stats.defence.base = level,
-        Skill::Ranged => app_state.player.stats.ranged.base = level,
-        Skill::Magic => app_state.player.stats.magic.base = level,
-        Skill::Hitpoints => app_state.player.stats.hitpoints.base = level,
-        Skill::Prayer => app_state.player.stats.prayer.base = level,
-        Skill::Mining => app_state.player.stats.mining.base = level,
-        Skill::Herblore => app_state.player.stats.herblore.base = level,
+#[component]
+fn TotalLevelDisplay() -> Element {
+    let app_state = use_context::<Signal<AppState>>();
+    let total: u32 = SKILLS_TAB_LAYOUT
+        .iter()
+        .map(|&skill| app_state.read().base_level(skill))
+        .sum();
+
+    rsx! {
+        div { class: "flex items-center justify-center gap-1 p-1 rounded bg-gray-800/50 text-sm",
+            span { class: "text-subtle", "Total" }
+            span { class: "font-bold", "{total}" }
+        }
+    }
+}
+
+#[component]
+fn CombatLevelDisplay() -> Element {
+    let app_state = use_context::<Signal<AppState>>();
+    let state = app_state.read();
+    let level = |skill: Skill| state.base_level(skill);
+    let combat = combat_level(level);
+    let next = next_combat_level(level);
+
+    rsx! {
+        div { class: "mt-2 text-sm text-center",
+            div {
+                span { class: "text-subtle", "Combat level: " }
+                span { class: "font-bold", "{combat.floor()}" }
+                span { class: "text-subtle text-xs", {format!(" ({combat:.2})")} }
+            }
+            if let Some((skills, levels)) = next {
+                div { class: "text-xs text-muted",
+                    {
+                        let names = skills.iter().map(|skill| skill.name()).collect::<Vec<_>>().join(" or ");
+                        let plural = if levels == 1 { "" } else { "s" };
+                        format!("Next combat level: {levels} {names} level{plural}")
+                    }
+                }
+            }
+        }
     }
-    app_state.player.reset_current_stats(true);
 }
@@ -0,0 +1,106 @@
+use crate::calc::{fight_stats, weapon_name, CombatClass};
+use crate::skill::Skill;
+use crate::spells::{Spell, Spellbook, SPELLS};
+use crate::state::AppState;
+use dioxus::prelude::*;
+
+/// A spell with its max hit, DPS, whether it's on-element and whether the
+/// player's weapon can cast it.
+type RankedSpell = (Spell, Option<u32>, f64, bool, bool);
+
+/// Spells on the player's spellbook, ranked by DPS against the selected
+/// monster including its elemental weakness. Spells needing a staff the player
+/// isn't wielding are listed last and can't be picked.
+#[component]
+pub fn SpellSelect() -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+
+    let (ranked, spellbook): (Vec<RankedSpell>, Spellbook) = {
+        let state = app_state.read();
+        let Some(monster) = state.target() else {
+            return rsx! {};
+        };
+        // Spells only matter for magic without a powered staff
+        let offence = fight_stats(&state.player, &monster, SPELLS.first().copied()).offence;
+        if offence.class != CombatClass::Magic || offence.spell.is_none() {
+            return rsx! {};
+        }
+
+        let magic_level = state.base_level(Skill::Magic);
+        let weapon = weapon_name(&state.player);
+        let mut ranked: Vec<_> = SPELLS
+            .iter()
+            .filter(|spell| spell.spellbook == state.spellbook && spell.level <= magic_level)
+            .map(|&spell| {
+                let stats = fight_stats(&state.player, &monster, Some(spell));
+                (
+                    spell,
+                    stats.offence.max_hit,
+                    stats.dps.unwrap_or_default(),
+                    stats.on_element,
+                    spell.castable_with(&weapon),
+                )
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.4.cmp(&a.4).then(b.2.total_cmp(&a.2)));
+        (ranked, state.spellbook)
+    };
+    let selected = app_state.read().spell;
+
+    rsx! {
+        div { class: "panel-elevated p-3 mt-2 text-sm",
+            div { class: "flex items-center justify-between mb-2",
+                h4 { class: "text-xs font-semibold text-muted", "Spells" }
+                select {
+                    class: "input h-7 py-0 px-2 text-xs",
+                    onchange: move |evt| {
+                        let mut state = app_state.write();
+                        state.spellbook = Spellbook::ALL
+                            .into_iter()
+                            .find(|book| book.name() == evt.value())
+                            .unwrap_or_default();
+                        // Drop a spell picked from the other spellbook
+                        let spellbook = state.spellbook;
+                        if state.spell.is_some_and(|spell| spell.spellbook != spellbook) {
+                            state.spell = None;
+                        }
+                    },
+                    for book in Spellbook::ALL {
+                        option {
+                            value: "{book.name()}",
+                            selected: spellbook == book,
+                            "{book.name()}"
+                        }
+                    }
+                }
+            }
+            div { class: "flex flex-col gap-1 max-h-48 overflow-y-auto",
+                for (spell, max_hit, dps, on_element, castable) in ranked {
+                    button {
+                        key: "{spell.name}",
+                        "type": "button",
+                        disabled: !castable,
+                        title: if castable { String::new() } else { format!("Needs {}", spell.staves.join(" or ")) },
+                        class: "flex items-center justify-between gap-2 px-2 py-1 rounded text-left hover:bg-gray-700",
+                        class: if selected == Some(spell) { "bg-gray-700" } else { "bg-gray-800/50" },
+                        class: if !castable { "opacity-50 cursor-not-allowed" },
+                        onclick: move |_| app_state.write().spell = Some(spell),
+                        span { class: "flex-grow",
+                            "{spell.name}"
+                            if on_element {
+                                span { class: "ml-1 text-[10px] px-1 rounded bg-green-600/30 text-green-300",
+                                    "on-element"
+                                }
+                            }
+                            if !castable {
+                                span { class: "ml-1 text-[10px] text-subtle", "needs staff" }
+                            }
+                        }
+                        span { class: "text-xs text-muted", "Max {max_hit.unwrap_or_default()}" }
+                        span { class: "text-xs font-bold w-14 text-right", {format!("{dps:.3}")} }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use crate::raids::{ToaContext, TOA_INVOCATIONS, TOA_MAX_PARTY_SIZE, TOA_MAX_PATH_LEVEL};
+use crate::state::AppState;
+use dioxus::prelude::*;
+
+/// Label, maximum, getter and setter of a raid setting.
+type ToaField = (
+    &'static str,
+    u32,
+    fn(&ToaContext) -> u32,
+    fn(&mut ToaContext, u32),
+);
+
+const TOA_FIELDS: [ToaField; 2] = [
+    (
+        "Path level",
+        TOA_MAX_PATH_LEVEL,
+        |t| t.path_level,
+        |t, v| t.path_level = v,
+    ),
+    (
+        "Party size",
+        TOA_MAX_PARTY_SIZE,
+        |t| t.party_size,
+        |t, v| t.party_size = v.max(1),
+    ),
+];
+
+/// Invocations, path level and party size for a Tombs of Amascut monster,
+/// shown only while one is selected.
+#[component]
+pub fn ToaContextPanel() -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+
+    let (toa, base, scaled) = {
+        let state = app_state.read();
+        let Some(monster) = state.monster.as_ref() else {
+            return rsx! {};
+        };
+        if !ToaContext::applies_to(&monster.edited) {
+            return rsx! {};
+        }
+        let scaled = state.target().map_or((0, 0), |target| {
+            (target.max_hp(), target.edited.stats.defence)
+        });
+        (
+            state.toa,
+            (monster.max_hp(), monster.edited.stats.defence),
+            scaled,
+        )
+    };
+
+    rsx! {
+        div { class: "panel-elevated p-3 mt-2 text-sm",
+            label { class: "flex items-center gap-2 cursor-pointer",
+                input {
+                    "type": "checkbox",
+                    checked: toa.enabled,
+                    onchange: move |evt| app_state.write().toa.enabled = evt.checked(),
+                }
+                span { class: "text-xs font-semibold text-muted", "Tombs of Amascut scaling" }
+            }
+            if toa.enabled {
+                div { class: "flex flex-col gap-1 mt-2 text-xs",
+                    for (label, max, get, set) in TOA_FIELDS {
+                        div { class: "flex items-center justify-between gap-2",
+                            span { class: "text-muted", "{label}" }
+                            input {
+                                "type": "number",
+                                class: "input w-20 h-7 text-center text-sm px-1 py-0",
+                                min: "0",
+                                max: "{max}",
+                                value: "{get(&toa)}",
+                                oninput: move |evt| {
+                                    if let Ok(value) = evt.value().parse::<u32>() {
+                                        set(&mut app_state.write().toa, value.min(max));
+                                    }
+                                },
+                            }
+                        }
+                    }
+                    div { class: "flex items-center justify-between gap-2 mt-1",
+                        span { class: "text-muted", "Invocations" }
+                        span { class: "font-bold", "Raid level {toa.raid_level()}" }
+                    }
+                    div { class: "grid grid-cols-2 gap-x-3 gap-y-0.5",
+                        for (index, invocation) in TOA_INVOCATIONS.iter().enumerate() {
+                            label { class: "flex items-center gap-1 cursor-pointer",
+                                input {
+                                    "type": "checkbox",
+                                    checked: toa.invocations[index],
+                                    onchange: move |evt| {
+                                        app_state.write().toa.set_invocation(index, evt.checked());
+                                    },
+                                }
+                                span { class: "truncate", "{invocation.name}" }
+                                span { class: "text-subtle ml-auto", "{invocation.raid_level}" }
+                            }
+                        }
+                    }
+                    div { class: "text-subtle mt-1",
+                        "Hitpoints {base.0} → "
+                        span { class: "font-bold text-accent", "{scaled.0}" }
+                        ", Defence {base.1} → "
+                        span { class: "font-bold text-accent", "{scaled.1}" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use crate::calc::next_breakpoints;
+use crate::skill::format_xp;
+use crate::state::AppState;
+use dioxus::prelude::*;
+
+#[component]
+pub fn TrainingPlanner() -> Element {
+    let app_state = use_context::<Signal<AppState>>();
+    let breakpoints = {
+        let state = app_state.read();
+        let target_defence_roll = state
+            .target()
+            .map(|monster| monster.edited.defence_roll(state.player.combat_type()));
+        next_breakpoints(
+            &state.player,
+            state.spell,
+            |skill| state.xp(skill),
+            target_defence_roll,
+        )
+    };
+
+    rsx! {
+        div { class: "mt-3 text-sm",
+            h4 { class: "text-xs font-semibold text-muted mb-1", "Next breakpoints" }
+            if breakpoints.is_empty() {
+                div { class: "text-xs text-subtle", "No further breakpoints for this setup." }
+            }
+            for breakpoint in breakpoints {
+                div { class: "flex items-center justify-between gap-2 p-1 rounded bg-gray-800/50 mb-1",
+                    img {
+                        class: "w-4 h-4 object-contain",
+                        src: "{breakpoint.skill.icon_path()}",
+                        alt: "{breakpoint.skill.name()}",
+                        title: "{breakpoint.skill.name()}",
+                    }
+                    span { class: "flex-grow", "Level {breakpoint.level}: {breakpoint.description}" }
+                    span { class: "text-xs text-subtle whitespace-nowrap",
+                        "{format_xp(breakpoint.xp_needed)} xp"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use crate::state::AppState;
+use crate::supplies::{Supply, TripSetup, INVENTORY_SLOTS};
+use dioxus::prelude::*;
+
+/// Label, getter and setter of an editable trip setting.
+type TripField = (&'static str, fn(&TripSetup) -> u32, fn(&mut TripSetup, u32));
+
+const TRIP_FIELDS: [TripField; 8] = [
+    ("Food slots", |t| t.food_slots, |t, v| t.food_slots = v),
+    ("Heals per food", |t| t.food_heal, |t, v| t.food_heal = v),
+    (
+        "Prayer potion slots",
+        |t| t.prayer_slots,
+        |t, v| t.prayer_slots = v,
+    ),
+    (
+        "Boost potion slots",
+        |t| t.boost_slots,
+        |t, v| t.boost_slots = v,
+    ),
+    ("Banking time (s)", |t| t.bank_secs, |t, v| t.bank_secs = v),
+    (
+        "Bank every (min, 0 = when out)",
+        |t| t.bank_every_mins,
+        |t, v| t.bank_every_mins = v,
+    ),
+    (
+        "Respawn (ticks)",
+        |t| t.respawn_ticks,
+        |t, v| t.respawn_ticks = v,
+    ),
+    (
+        "Idle between kills (ticks)",
+        |t| t.idle_ticks,
+        |t, v| t.idle_ticks = v,
+    ),
+];
+
+/// Food, prayer and boost doses used per kill of the selected monster, and
+/// how many kills an inventory lasts.
+#[component]
+pub fn TripResults() -> Element {
+    let mut app_state = use_context::<Signal<AppState>>();
+    let mut is_collapsed = use_signal(|| true);
+
+    let Some(estimate) = app_state.read().trip_estimate() else {
+        return rsx! {};
+    };
+    let setup = app_state.read().trip;
+    let per_kill = estimate.per_kill;
+    let over_capacity = setup.used_slots() > INVENTORY_SLOTS;
+
+    rsx! {
+        div { class: "panel-elevated p-3 mt-2 text-sm",
+            div {
+                class: "flex items-center justify-between cursor-pointer",
+                onclick: move |_| is_collapsed.set(!is_collapsed()),
+                h4 { class: "text-xs font-semibold text-muted", "Supplies and trips" }
+                span {
+                    class: "text-muted text-xs transition-transform duration-200",
+                    class: if !is_collapsed() { "rotate-180" },
+                    "▼"
+                }
+            }
+
+            div { class: "grid grid-cols-2 gap-x-4 gap-y-1 mt-2",
+                span { class: "text-muted", "Food per kill" }
+                span { class: "text-right", {format!("{:.2}", per_kill.food)} }
+                span { class: "text-muted", "Prayer doses per kill" }
+                span { class: "text-right", {format!("{:.2}", per_kill.prayer_doses)} }
+                span { class: "text-muted", "Boost doses per kill" }
+                span { class: "text-right", {format!("{:.2}", per_kill.boost_doses)} }
+                span { class: "text-muted", "Kills per trip" }
+                span { class: "text-right font-bold",
+                    {estimate.kills_per_trip.map_or("Unlimited".to_string(), |kills| format!("{kills:.0}"))}
+                }
+                if let Some(secs) = estimate.trip_secs {
+                    span { class: "text-muted", "Trip length" }
+                    span { class: "text-right", {format!("{:.1} min", secs / 60.0)} }
+                }
+            }
+            if let Some(supply) = estimate.limited_by {
+                div { class: "text-xs text-subtle mt-1",
+                    if supply == Supply::Interval {
+                        "Trips end at the banking interval."
+                    } else {
+                        "Trips end when the {supply.name()} run out."
+                    }
+                }
+            }
+
+            if !is_collapsed() {
+                div { class: "flex flex-col gap-1 mt-2 text-xs",
+                    for (label, get, set) in TRIP_FIELDS {
+                        div { class: "flex items-center justify-between gap-2",
+                            span { class: "text-muted", "{label}" }
+                            input {
+                                "type": "number",
+                                class: "input w-20 h-7 text-center text-sm px-1 py-0",
+                                min: "0",
+                                value: "{get(&setup)}",
+                                oninput: move |evt| {
+                                    if let Ok(value) = evt.value().parse::<u32>() {
+                                        set(&mut app_state.write().trip, value);
+                                    }
+                                },
+                            }
+                        }
+                    }
+                    if over_capacity {
+                        div { class: "text-warning",
+                            "That's {setup.used_slots()} slots, but an inventory only has {INVENTORY_SLOTS}."
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,443 @@
+//! The selected monster's attacks against the player.
+//!
+//! Monsters roll `(level + 9) * (attack bonus + 64)` against the player's
+//! defence roll for the style. Protection prayers are treated as blocking all
+//! damage of their style, which overstates them against bosses that hit
+//! through prayer.
+//!
+//! Typeless attacks and dragonfire always hit, and are only included when
+//! monsters.json lists a max hit for them. Dragonfire follows the rules for
+//! ordinary dragons; bosses with their own dragonfire rules aren't modelled.
+
+use crate::boosts::TICK_SECONDS;
+use crate::calc::{average_hit, hit_chance, prayer_boosts};
+use crate::monster::MonsterJson;
+use osrs::types::equipment::{CombatStance, GearSlot};
+use osrs::types::player::Player;
+
+/// Most dragonfire gets through with one kind of protection.
+const PARTIAL_DRAGONFIRE_MAX_HIT: u32 = 10;
+/// Shields that protect against dragonfire, including their variants.
+const DRAGONFIRE_SHIELDS: [&str; 4] = [
+    "Anti-dragon shield",
+    "Dragonfire shield",
+    "Dragonfire ward",
+    "Ancient wyvern shield",
+];
+
+/// How a monster attack is defended against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MonsterStyle {
+    Stab,
+    Slash,
+    Crush,
+    Ranged,
+    Magic,
+    /// Dragonfire and wyvern breath, which shields and antifires protect
+    /// against.
+    Dragonfire,
+    /// Other attacks that ignore defence.
+    Typeless,
+}
+
+impl MonsterStyle {
+    /// Parses an attack style as written in monsters.json. Generic melee is
+    /// treated as crush, and styles that can't be defended as typeless.
+    /// Poison attacks are left out, as they have no max hit of their own.
+    pub fn from_json(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        let style = if name.contains("poison") {
+            return None;
+        } else if name.contains("dragonfire") || name.contains("breath") {
+            MonsterStyle::Dragonfire
+        } else if name.contains("typeless") {
+            MonsterStyle::Typeless
+        } else if name.contains("stab") {
+            MonsterStyle::Stab
+        } else if name.contains("slash") {
+            MonsterStyle::Slash
+        } else if name.contains("crush") {
+            MonsterStyle::Crush
+        } else if name.contains("magic") {
+            MonsterStyle::Magic
+        } else if name.contains("ranged") {
+            MonsterStyle::Ranged
+        } else if name.contains("melee") {
+            MonsterStyle::Crush
+        } else {
+            return None;
+        };
+        Some(style)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MonsterStyle::Stab => "Stab",
+            MonsterStyle::Slash => "Slash",
+            MonsterStyle::Crush => "Crush",
+            MonsterStyle::Ranged => "Ranged",
+            MonsterStyle::Magic => "Magic",
+            MonsterStyle::Dragonfire => "Dragonfire",
+            MonsterStyle::Typeless => "Typeless",
+        }
+    }
+
+    // Words used for this style in max hit notes, e.g. "66 (Melee)"
+    fn keywords(&self) -> &'static [&'static str] {
+        match self {
+            MonsterStyle::Stab | MonsterStyle::Slash | MonsterStyle::Crush => {
+                &["melee", "stab", "slash", "crush"]
+            }
+            MonsterStyle::Ranged => &["ranged"],
+            MonsterStyle::Magic => &["magic"],
+            MonsterStyle::Dragonfire => &["dragonfire", "breath"],
+            MonsterStyle::Typeless => &["typeless"],
+        }
+    }
+
+    /// Whether the style ignores the player's defence.
+    pub fn always_hits(&self) -> bool {
+        matches!(self, MonsterStyle::Dragonfire | MonsterStyle::Typeless)
+    }
+}
+
+/// Antifire potion the player is under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Antifire {
+    #[default]
+    None,
+    /// An antifire or extended antifire.
+    Regular,
+    /// A super or extended super antifire, which blocks dragonfire on its own.
+    Super,
+}
+
+impl Antifire {
+    pub const ALL: [Antifire; 3] = [Antifire::None, Antifire::Regular, Antifire::Super];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Antifire::None => "No antifire",
+            Antifire::Regular => "Antifire",
+            Antifire::Super => "Super antifire",
+        }
+    }
+}
+
+fn has_dragonfire_shield(player: &Player) -> bool {
+    player.get_slot(&GearSlot::Shield).is_some_and(|shield| {
+        let name = shield.name();
+        DRAGONFIRE_SHIELDS
+            .iter()
+            .any(|shield| name.starts_with(shield))
+    })
+}
+
+/// Max hit of dragonfire listed at `max_hit` after the player's protection.
+/// A super antifire, or an antifire with a shield, blocks it; a shield, an
+/// antifire or Protect from Magic alone caps it at 10.
+pub fn dragonfire_max_hit(
+    max_hit: u32,
+    shield: bool,
+    antifire: Antifire,
+    protection: Option<Protection>,
+) -> u32 {
+    let praying = protection == Some(Protection::Magic);
+    match antifire {
+        Antifire::Super => 0,
+        Antifire::Regular if shield => 0,
+        Antifire::Regular => max_hit.min(PARTIAL_DRAGONFIRE_MAX_HIT),
+        Antifire::None if shield || praying => max_hit.min(PARTIAL_DRAGONFIRE_MAX_HIT),
+        Antifire::None => max_hit,
+    }
+}
+
+/// Overhead protection prayers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Protection {
+    Melee,
+    Missiles,
+    Magic,
+}
+
+impl Protection {
+    pub const ALL: [Protection; 3] = [Protection::Melee, Protection::Missiles, Protection::Magic];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Protection::Melee => "Protect from Melee",
+            Protection::Missiles => "Protect from Missiles",
+            Protection::Magic => "Protect from Magic",
+        }
+    }
+
+    pub fn blocks(&self, style: MonsterStyle) -> bool {
+        match self {
+            Protection::Melee => matches!(
+                style,
+                MonsterStyle::Stab | MonsterStyle::Slash | MonsterStyle::Crush
+            ),
+            Protection::Missiles => style == MonsterStyle::Ranged,
+            Protection::Magic => style == MonsterStyle::Magic,
+        }
+    }
+}
+
+/// One of the monster's attack styles against the player.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonsterAttack {
+    pub style: MonsterStyle,
+    pub max_hit: u32,
+    pub attack_roll: u32,
+    /// The player's defence roll, or 0 for typeless attacks.
+    pub defence_roll: u32,
+    pub hit_chance: f64,
+    /// Whether the active protection prayer blocks it.
+    pub protected: bool,
+    pub damage_per_tick: f64,
+}
+
+impl MonsterAttack {
+    pub fn damage_per_second(&self) -> f64 {
+        self.damage_per_tick / TICK_SECONDS
+    }
+}
+
+/// Max hits from monsters.json, with any note in brackets, e.g. "66 (Melee)".
+fn parse_max_hits(monster: &MonsterJson) -> Vec<(u32, String)> {
+    monster
+        .max_hit
+        .iter()
+        .flatten()
+        .filter_map(|entry| {
+            let digits: String = entry
+                .trim()
+                .chars()
+                .take_while(char::is_ascii_digit)
+                .collect();
+            Some((digits.parse().ok()?, entry.to_lowercase()))
+        })
+        .collect()
+}
+
+/// The max hit listed for a style, or `None` when there isn't one. A monster
+/// with a single style uses whatever max hit is listed first.
+fn max_hit_for(max_hits: &[(u32, String)], style: MonsterStyle, only_style: bool) -> Option<u32> {
+    max_hits
+        .iter()
+        .find(|(_, note)| {
+            style
+                .keywords()
+                .iter()
+                .any(|keyword| note.contains(keyword))
+        })
+        .or(max_hits.first().filter(|_| only_style))
+        .map(|(hit, _)| *hit)
+}
+
+/// The player's defence roll against a style, with defence prayers and the
+/// stance's invisible Defence bonus. Magic is defended with 70% Magic and 30%
+/// Defence.
+pub fn player_defence_roll(player: &Player, style: MonsterStyle) -> u32 {
+    let prayers = prayer_boosts(player);
+    let scale = |level: u32, percent: u32| level * (100 + percent) / 100;
+    let stance_bonus = match player.combat_stance() {
+        CombatStance::Defensive | CombatStance::Longrange => 3,
+        CombatStance::Controlled => 1,
+        _ => 0,
+    };
+    let eff_def = scale(player.stats.defence.current, prayers.defence) + stance_bonus;
+    let defence = &player.bonuses.defence;
+    let (level, bonus) = match style {
+        MonsterStyle::Stab => (eff_def, defence.stab),
+        MonsterStyle::Slash => (eff_def, defence.slash),
+        MonsterStyle::Crush => (eff_def, defence.crush),
+        MonsterStyle::Ranged => (eff_def, defence.ranged),
+        MonsterStyle::Magic => {
+            let eff_magic = scale(player.stats.magic.current, prayers.magic_attack);
+            ((eff_magic * 7 + eff_def * 3) / 10, defence.magic)
+        }
+        MonsterStyle::Dragonfire | MonsterStyle::Typeless => return 0,
+    };
+    (level + 8) * (bonus + 64).max(0) as u32
+}
+
+/// Each distinct style the monster attacks with, as listed in monsters.json.
+pub fn attack_styles(monster: &MonsterJson) -> Vec<MonsterStyle> {
+    let mut styles: Vec<MonsterStyle> = Vec::new();
+    for style in monster.info.attack_styles.iter().flatten() {
+        if let Some(style) = MonsterStyle::from_json(style) {
+            if !styles.contains(&style) {
+                styles.push(style);
+            }
+        }
+    }
+    styles
+}
+
+/// Each distinct style the monster attacks with that has a known max hit, and
+/// what it does to the player.
+pub fn monster_attacks(
+    player: &Player,
+    monster: &MonsterJson,
+    protection: Option<Protection>,
+    antifire: Antifire,
+) -> Vec<MonsterAttack> {
+    let max_hits = parse_max_hits(monster);
+    let styles = attack_styles(monster);
+    let only_style = styles.len() == 1;
+    let shield = has_dragonfire_shield(player);
+
+    let stats = &monster.stats;
+    let attack = &monster.bonuses.attack;
+    let attack_speed = monster.info.attack_speed.max(1) as f64;
+    styles
+        .into_iter()
+        .filter_map(|style| {
+            let listed_max_hit = max_hit_for(&max_hits, style, only_style)?;
+            let (level, bonus) = match style {
+                MonsterStyle::Ranged => (stats.ranged, attack.ranged),
+                MonsterStyle::Magic => (stats.magic, attack.magic),
+                _ => (stats.attack, attack.melee),
+            };
+            let attack_roll = ((level + 9).max(0) * (bonus + 64).max(0)) as u32;
+            let defence_roll = player_defence_roll(player, style);
+            let chance = if style.always_hits() {
+                1.0
+            } else {
+                hit_chance(attack_roll, defence_roll)
+            };
+            let max_hit = match style {
+                MonsterStyle::Dragonfire => {
+                    dragonfire_max_hit(listed_max_hit, shield, antifire, protection)
+                }
+                _ => listed_max_hit,
+            };
+            let protected = protection.is_some_and(|prayer| prayer.blocks(style));
+            let damage_per_tick = if protected {
+                0.0
+            } else {
+                chance * average_hit(max_hit, 0) / attack_speed
+            };
+            Some(MonsterAttack {
+                style,
+                max_hit,
+                attack_roll,
+                defence_roll,
+                hit_chance: chance,
+                protected,
+                damage_per_tick,
+            })
+        })
+        .collect()
+}
+
+/// Expected damage per tick assuming the monster picks evenly between its
+/// styles.
+pub fn average_damage_per_tick(attacks: &[MonsterAttack]) -> f64 {
+    if attacks.is_empty() {
+        return 0.0;
+    }
+    attacks
+        .iter()
+        .map(|attack| attack.damage_per_tick)
+        .sum::<f64>()
+        / attacks.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monster(styles: &[&str], max_hits: &[&str]) -> MonsterJson {
+        let mut monster = MonsterJson::blank(-1);
+        monster.info.attack_styles = Some(styles.iter().map(|style| style.to_string()).collect());
+        monster.max_hit = Some(max_hits.iter().map(|hit| hit.to_string()).collect());
+        monster
+    }
+
+    #[test]
+    fn parses_styles() {
+        assert_eq!(MonsterStyle::from_json("Slash"), Some(MonsterStyle::Slash));
+        assert_eq!(MonsterStyle::from_json("Melee"), Some(MonsterStyle::Crush));
+        assert_eq!(
+            MonsterStyle::from_json("dragonfire"),
+            Some(MonsterStyle::Dragonfire)
+        );
+        assert_eq!(
+            MonsterStyle::from_json("Icy breath"),
+            Some(MonsterStyle::Dragonfire)
+        );
+        assert_eq!(
+            MonsterStyle::from_json("Typeless"),
+            Some(MonsterStyle::Typeless)
+        );
+        assert_eq!(MonsterStyle::from_json("Poison"), None);
+    }
+
+    #[test]
+    fn dragonfire_protection() {
+        // Green dragons breathe up to 50
+        assert_eq!(dragonfire_max_hit(50, false, Antifire::None, None), 50);
+        assert_eq!(dragonfire_max_hit(50, true, Antifire::None, None), 10);
+        assert_eq!(dragonfire_max_hit(50, false, Antifire::Regular, None), 10);
+        assert_eq!(
+            dragonfire_max_hit(50, false, Antifire::None, Some(Protection::Magic)),
+            10
+        );
+        assert_eq!(dragonfire_max_hit(50, true, Antifire::Regular, None), 0);
+        assert_eq!(dragonfire_max_hit(50, false, Antifire::Super, None), 0);
+    }
+
+    #[test]
+    fn leaves_out_styles_without_a_max_hit() {
+        let player = Player::default();
+        // Adamant dragon, with its dragonfire noted
+        let adamant = monster(
+            &["Slash", "Ranged", "Magic", "dragonfire"],
+            &["29 (Melee)", "20 (Ranged)", "20 (Magic)", "50 (Dragonfire)"],
+        );
+        let attacks = monster_attacks(&player, &adamant, None, Antifire::None);
+        let max_hits: Vec<_> = attacks.iter().map(|a| (a.style, a.max_hit)).collect();
+        assert_eq!(
+            max_hits,
+            [
+                (MonsterStyle::Slash, 29),
+                (MonsterStyle::Ranged, 20),
+                (MonsterStyle::Magic, 20),
+                (MonsterStyle::Dragonfire, 50),
+            ]
+        );
+
+        // A typeless attack whose max hit isn't given is dropped, not guessed
+        let unnoted = monster(&["Crush", "Typeless"], &["16 (Melee)"]);
+        let attacks = monster_attacks(&player, &unnoted, None, Antifire::None);
+        assert_eq!(attacks.len(), 1);
+        assert_eq!(attacks[0].style, MonsterStyle::Crush);
+
+        // A single style takes whatever is listed
+        let single = monster(&["Magic"], &["16"]);
+        let attacks = monster_attacks(&player, &single, None, Antifire::None);
+        assert_eq!(attacks[0].max_hit, 16);
+    }
+
+    #[test]
+    fn typeless_always_hits_through_prayer() {
+        let player = Player::default();
+        let blood = monster(&["Typeless"], &["20 (Typeless)"]);
+        let attacks = monster_attacks(&player, &blood, Some(Protection::Magic), Antifire::None);
+        assert_eq!(attacks[0].hit_chance, 1.0);
+        assert!(!attacks[0].protected);
+        // Damage 0-20 averages 10, every 4 ticks
+        assert!((attacks[0].damage_per_tick - 2.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn protection_blocks_its_style() {
+        let player = Player::default();
+        let mage = monster(&["Magic"], &["30"]);
+        let attacks = monster_attacks(&player, &mage, Some(Protection::Magic), Antifire::None);
+        assert!(attacks[0].protected);
+        assert_eq!(average_damage_per_tick(&attacks), 0.0);
+    }
+}
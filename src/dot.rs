@@ -0,0 +1,274 @@
+//! Damage over time from poison, venom and burns.
+//!
+//! These are estimates: each effect is assumed to be applied at the expected
+//! time of its first successful proc and then kept up for the rest of the
+//! fight.
+
+use crate::boosts::TICK_SECONDS;
+use crate::calc::CombatClass;
+use crate::monster::{BurnStrength, MonsterImmunities};
+use osrs::types::equipment::GearSlot;
+use osrs::types::player::Player;
+
+/// Poison and venom deal damage every 30 ticks.
+const POISON_INTERVAL_TICKS: f64 = 30.0;
+/// Venom starts at 6 and rises by 2 each time it deals damage, up to 20.
+const VENOM_START: u32 = 6;
+const VENOM_STEP: u32 = 2;
+const VENOM_MAX: u32 = 20;
+/// A burn deals 1 damage every 4 ticks, 10 times.
+const BURN_INTERVAL_TICKS: f64 = 4.0;
+const BURN_HITS: f64 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DotKind {
+    /// Poison with the damage it deals each time.
+    Poison(u32),
+    Venom,
+    Burn(BurnStrength),
+}
+
+impl DotKind {
+    pub fn name(&self) -> String {
+        match self {
+            DotKind::Poison(damage) => format!("Poison ({damage})"),
+            DotKind::Venom => "Venom".to_string(),
+            DotKind::Burn(strength) => format!("Burn ({})", strength.name()),
+        }
+    }
+}
+
+/// An equipped item that can inflict damage over time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DotSource {
+    pub item: String,
+    pub kind: DotKind,
+    /// Chance of inflicting it on a successful hit.
+    pub chance: f64,
+}
+
+const ALL_STYLES: &[CombatClass] = &[CombatClass::Melee, CombatClass::Ranged, CombatClass::Magic];
+
+// Venomous items, with the chance of envenoming on a hit and the styles they
+// envenom with
+const VENOM_ITEMS: [(&str, f64, &[CombatClass]); 7] = [
+    ("Toxic blowpipe", 0.25, &[CombatClass::Ranged]),
+    ("Serpentine helm", 1.0 / 6.0, ALL_STYLES),
+    ("Tanzanite helm", 1.0 / 6.0, ALL_STYLES),
+    ("Magma helm", 1.0 / 6.0, ALL_STYLES),
+    ("Toxic staff of the dead", 0.25, &[CombatClass::Melee]),
+    ("Trident of the swamp", 0.25, &[CombatClass::Magic]),
+    ("Trident of the swamp (e)", 0.25, &[CombatClass::Magic]),
+];
+
+// Slots that can hold an item inflicting damage over time
+const DOT_SLOTS: [GearSlot; 3] = [GearSlot::Head, GearSlot::Weapon, GearSlot::Ammo];
+
+// Burning items, with the burn they inflict and the styles they burn with.
+// Salamanders burn with their melee, ranged and magic attacks alike
+const BURN_ITEMS: [(&str, BurnStrength, &[CombatClass]); 5] = [
+    ("Swamp lizard", BurnStrength::Weak, ALL_STYLES),
+    ("Orange salamander", BurnStrength::Weak, ALL_STYLES),
+    ("Red salamander", BurnStrength::Normal, ALL_STYLES),
+    ("Black salamander", BurnStrength::Normal, ALL_STYLES),
+    ("Tecu salamander", BurnStrength::Strong, ALL_STYLES),
+];
+const BURN_CHANCE: f64 = 0.2;
+const POISON_CHANCE: f64 = 0.25;
+
+/// Items in the loadout that can inflict damage over time with this style.
+pub fn dot_sources(player: &Player, class: CombatClass) -> Vec<DotSource> {
+    DOT_SLOTS
+        .iter()
+        .filter_map(|slot| {
+            let item = player.get_slot(slot)?.name().to_string();
+            item_source(item, slot, class)
+        })
+        .collect()
+}
+
+// What an item in a slot inflicts with this style, if anything
+fn item_source(item: String, slot: &GearSlot, class: CombatClass) -> Option<DotSource> {
+    if let Some(&(_, chance, styles)) = VENOM_ITEMS.iter().find(|(name, ..)| *name == item) {
+        return styles.contains(&class).then_some(DotSource {
+            item,
+            kind: DotKind::Venom,
+            chance,
+        });
+    }
+    if let Some(&(_, strength, styles)) = BURN_ITEMS.iter().find(|(name, ..)| *name == item) {
+        return styles.contains(&class).then_some(DotSource {
+            item,
+            kind: DotKind::Burn(strength),
+            chance: BURN_CHANCE,
+        });
+    }
+    let level = poison_level(&item)?;
+    // Ammo only poisons when it is being fired
+    if matches!(slot, GearSlot::Ammo) && class != CombatClass::Ranged {
+        return None;
+    }
+    // Poisoned ranged weapons and ammo are weaker than melee weapons
+    let damage = match class {
+        CombatClass::Melee => 3 + level,
+        _ => 1 + level,
+    };
+    Some(DotSource {
+        item,
+        kind: DotKind::Poison(damage),
+        chance: POISON_CHANCE,
+    })
+}
+
+// 1, 2 or 3 for items ending in (p), (p+) or (p++)
+fn poison_level(item: &str) -> Option<u32> {
+    let item = item.trim_end();
+    if item.ends_with("(p++)") {
+        Some(3)
+    } else if item.ends_with("(p+)") {
+        Some(2)
+    } else if item.ends_with("(p)") {
+        Some(1)
+    } else {
+        None
+    }
+}
+
+/// What a source actually inflicts on a monster, or `None` when it is immune.
+/// Venom turns into poison against monsters that are only immune to venom.
+pub fn effective_kind(kind: DotKind, immunities: &MonsterImmunities) -> Option<DotKind> {
+    match kind {
+        DotKind::Venom if immunities.venom && !immunities.poison => {
+            Some(DotKind::Poison(VENOM_START))
+        }
+        DotKind::Venom if immunities.venom => None,
+        DotKind::Poison(_) if immunities.poison => None,
+        DotKind::Burn(strength) if immunities.blocks_burn(strength) => None,
+        kind => Some(kind),
+    }
+}
+
+/// Expected damage from one effect over a fight.
+///
+/// `proc_rate` is the chance per second of applying the effect, i.e. the
+/// chance per attack times hit chance divided by the attack interval.
+pub fn dot_damage(kind: DotKind, proc_rate: f64, fight_secs: f64) -> f64 {
+    if proc_rate <= 0.0 {
+        return 0.0;
+    }
+    let first_proc = 1.0 / proc_rate;
+    let active_secs = fight_secs - first_proc;
+    if active_secs <= 0.0 {
+        return 0.0;
+    }
+
+    match kind {
+        DotKind::Poison(damage) => {
+            let hits = (active_secs / (POISON_INTERVAL_TICKS * TICK_SECONDS)).floor() + 1.0;
+            hits * damage as f64
+        }
+        DotKind::Venom => {
+            let hits = (active_secs / (POISON_INTERVAL_TICKS * TICK_SECONDS)).floor() as u32 + 1;
+            (0..hits)
+                .map(|hit| (VENOM_START + hit * VENOM_STEP).min(VENOM_MAX) as f64)
+                .sum()
+        }
+        DotKind::Burn(_) => {
+            // Burns are reapplied on later procs, so estimate how much of the
+            // fight has one active
+            let burn_secs = BURN_HITS * BURN_INTERVAL_TICKS * TICK_SECONDS;
+            let uptime = (proc_rate * burn_secs).min(1.0);
+            active_secs * uptime / (BURN_INTERVAL_TICKS * TICK_SECONDS)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Applied straight away, so the effect runs for the whole fight
+    const ALWAYS: f64 = 1e9;
+
+    fn immunities(poison: bool, venom: bool, burn: Option<&str>) -> MonsterImmunities {
+        MonsterImmunities {
+            poison,
+            venom,
+            freeze: 0,
+            burn: burn.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn poison_levels_from_item_names() {
+        assert_eq!(poison_level("Dragon dagger(p++)"), Some(3));
+        assert_eq!(poison_level("Rune arrow(p+)"), Some(2));
+        assert_eq!(poison_level("Bronze dart(p)"), Some(1));
+        assert_eq!(poison_level("Dragon dagger"), None);
+    }
+
+    #[test]
+    fn serpentine_helm_envenoms_one_hit_in_six() {
+        let (_, chance, styles) = VENOM_ITEMS[1];
+        assert_eq!(chance, 1.0 / 6.0);
+        assert_eq!(styles, ALL_STYLES);
+    }
+
+    #[test]
+    fn sources_follow_the_active_style() {
+        let source = |item: &str, slot, class| item_source(item.to_string(), &slot, class);
+
+        assert_eq!(
+            source("Dragon scimitar", GearSlot::Weapon, CombatClass::Melee),
+            None
+        );
+        assert_eq!(
+            source("Toxic blowpipe", GearSlot::Weapon, CombatClass::Melee),
+            None
+        );
+        assert_eq!(
+            source("Rune arrow(p++)", GearSlot::Ammo, CombatClass::Melee),
+            None
+        );
+        for class in [CombatClass::Melee, CombatClass::Ranged, CombatClass::Magic] {
+            assert_eq!(
+                source("Black salamander", GearSlot::Weapon, class).map(|s| s.kind),
+                Some(DotKind::Burn(BurnStrength::Normal))
+            );
+        }
+    }
+
+    #[test]
+    fn venom_hits_rise_by_two() {
+        // Hits at 0, 18, 36 and 54 seconds
+        assert_eq!(
+            dot_damage(DotKind::Venom, ALWAYS, 60.0),
+            6.0 + 8.0 + 10.0 + 12.0
+        );
+        assert_eq!(dot_damage(DotKind::Poison(4), ALWAYS, 37.0), 12.0);
+        assert_eq!(dot_damage(DotKind::Poison(4), 0.0, 37.0), 0.0);
+    }
+
+    #[test]
+    fn immunities_block_or_weaken_effects() {
+        let venom_only = immunities(false, true, None);
+        assert_eq!(
+            effective_kind(DotKind::Venom, &venom_only),
+            Some(DotKind::Poison(VENOM_START))
+        );
+        assert_eq!(
+            effective_kind(DotKind::Venom, &immunities(true, true, None)),
+            None
+        );
+
+        let weak_burns = immunities(false, false, Some("Immune to weak burns"));
+        assert_eq!(
+            effective_kind(DotKind::Burn(BurnStrength::Weak), &weak_burns),
+            None
+        );
+        assert_eq!(
+            effective_kind(DotKind::Burn(BurnStrength::Normal), &weak_burns),
+            Some(DotKind::Burn(BurnStrength::Normal))
+        );
+    }
+}
@@ -0,0 +1,391 @@
+use crate::calc::{equipped_names, CombatClass};
+use crate::monster::{MonsterAttribute, MonsterJson};
+use osrs::types::player::Player;
+
+const MELEE: &[CombatClass] = &[CombatClass::Melee];
+const RANGED: &[CombatClass] = &[CombatClass::Ranged];
+const MAGIC: &[CombatClass] = &[CombatClass::Magic];
+const MELEE_RANGED: &[CombatClass] = &[CombatClass::Melee, CombatClass::Ranged];
+const ALL_STYLES: &[CombatClass] = &[CombatClass::Melee, CombatClass::Ranged, CombatClass::Magic];
+
+/// An equipped item's bonus against monsters with an attribute.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GearEffect {
+    /// Item names, also matching variants written as "<name> (<variant>)".
+    pub items: &'static [&'static str],
+    pub attribute: MonsterAttribute,
+    pub styles: &'static [CombatClass],
+    /// Accuracy and damage multipliers as (numerator, denominator).
+    pub accuracy: (u32, u32),
+    pub damage: (u32, u32),
+}
+
+const NONE: (u32, u32) = (1, 1);
+
+// Later entries for the same item cover the styles earlier ones don't.
+// Vampyre effects are listed with the highest tier the weapon can harm.
+const GEAR_EFFECTS: [GearEffect; 22] = [
+    GearEffect {
+        items: &["Salve amulet"],
+        attribute: MonsterAttribute::Undead,
+        styles: MELEE,
+        accuracy: (7, 6),
+        damage: (7, 6),
+    },
+    GearEffect {
+        items: &["Salve amulet(i)"],
+        attribute: MonsterAttribute::Undead,
+        styles: MELEE_RANGED,
+        accuracy: (7, 6),
+        damage: (7, 6),
+    },
+    GearEffect {
+        items: &["Salve amulet(i)"],
+        attribute: MonsterAttribute::Undead,
+        styles: MAGIC,
+        accuracy: (23, 20),
+        damage: (23, 20),
+    },
+    GearEffect {
+        items: &["Salve amulet (e)"],
+        attribute: MonsterAttribute::Undead,
+        styles: MELEE,
+        accuracy: (6, 5),
+        damage: (6, 5),
+    },
+    GearEffect {
+        items: &["Salve amulet(ei)"],
+        attribute: MonsterAttribute::Undead,
+        styles: ALL_STYLES,
+        accuracy: (6, 5),
+        damage: (6, 5),
+    },
+    GearEffect {
+        items: &["Arclight", "Emberlight"],
+        attribute: MonsterAttribute::Demon,
+        styles: MELEE,
+        accuracy: (17, 10),
+        damage: (17, 10),
+    },
+    GearEffect {
+        items: &["Silverlight", "Darklight"],
+        attribute: MonsterAttribute::Demon,
+        styles: MELEE,
+        accuracy: (8, 5),
+        damage: (8, 5),
+    },
+    GearEffect {
+        items: &["Scorching bow"],
+        attribute: MonsterAttribute::Demon,
+        styles: RANGED,
+        accuracy: (13, 10),
+        damage: (13, 10),
+    },
+    GearEffect {
+        items: &["Dragon hunter crossbow"],
+        attribute: MonsterAttribute::Dragon,
+        styles: RANGED,
+        accuracy: (13, 10),
+        damage: (5, 4),
+    },
+    GearEffect {
+        items: &["Dragon hunter lance"],
+        attribute: MonsterAttribute::Dragon,
+        styles: MELEE,
+        accuracy: (6, 5),
+        damage: (6, 5),
+    },
+    GearEffect {
+        items: &["Dragon hunter wand"],
+        attribute: MonsterAttribute::Dragon,
+        styles: MAGIC,
+        accuracy: (3, 2),
+        damage: (6, 5),
+    },
+    GearEffect {
+        items: &[
+            "Keris",
+            "Keris partisan",
+            "Keris partisan of corruption",
+            "Keris partisan of the sun",
+        ],
+        attribute: MonsterAttribute::Kalphite,
+        styles: MELEE,
+        accuracy: NONE,
+        damage: (4, 3),
+    },
+    GearEffect {
+        items: &["Keris partisan of breaching"],
+        attribute: MonsterAttribute::Kalphite,
+        styles: MELEE,
+        accuracy: (4, 3),
+        damage: (4, 3),
+    },
+    GearEffect {
+        items: &["Leaf-bladed battleaxe"],
+        attribute: MonsterAttribute::Leafy,
+        styles: MELEE,
+        accuracy: NONE,
+        damage: (47, 40),
+    },
+    GearEffect {
+        items: &["Barronite mace"],
+        attribute: MonsterAttribute::Golem,
+        styles: MELEE,
+        accuracy: NONE,
+        damage: (23, 20),
+    },
+    // The 5% chance of a double damage hit isn't modelled
+    GearEffect {
+        items: &["Gadderhammer"],
+        attribute: MonsterAttribute::Shade,
+        styles: MELEE,
+        accuracy: NONE,
+        damage: (5, 4),
+    },
+    GearEffect {
+        items: &["Bone mace"],
+        attribute: MonsterAttribute::Rat,
+        styles: MELEE,
+        accuracy: NONE,
+        damage: (11, 10),
+    },
+    GearEffect {
+        items: &["Silver sickle (b)"],
+        attribute: MonsterAttribute::Vampyre(2),
+        styles: MELEE,
+        accuracy: NONE,
+        damage: NONE,
+    },
+    GearEffect {
+        items: &["Rod of ivandis"],
+        attribute: MonsterAttribute::Vampyre(2),
+        styles: MELEE,
+        accuracy: NONE,
+        damage: NONE,
+    },
+    GearEffect {
+        items: &["Ivandis flail"],
+        attribute: MonsterAttribute::Vampyre(3),
+        styles: MELEE,
+        accuracy: NONE,
+        damage: (6, 5),
+    },
+    GearEffect {
+        items: &["Blisterwood flail"],
+        attribute: MonsterAttribute::Vampyre(3),
+        styles: MELEE,
+        accuracy: (21, 20),
+        damage: (5, 4),
+    },
+    GearEffect {
+        items: &["Blisterwood sickle"],
+        attribute: MonsterAttribute::Vampyre(3),
+        styles: MELEE,
+        accuracy: (21, 20),
+        damage: (23, 20),
+    },
+];
+
+impl GearEffect {
+    fn matches_item(&self, name: &str) -> bool {
+        self.items.iter().any(|item| {
+            name == *item
+                || name
+                    .strip_prefix(item)
+                    .is_some_and(|rest| rest.starts_with(" ("))
+        })
+    }
+
+    // Vampyre effects apply up to the highest tier the weapon can harm
+    fn matches_target(&self, monster: &MonsterJson) -> bool {
+        monster
+            .attributes()
+            .iter()
+            .any(|attribute| match (self.attribute, attribute) {
+                (MonsterAttribute::Vampyre(max_tier), MonsterAttribute::Vampyre(tier)) => {
+                    *tier <= max_tier
+                }
+                (expected, attribute) => expected == *attribute,
+            })
+    }
+
+    // The attribute to name in warnings, without the tier of vampyre effects
+    fn target_attribute(&self) -> MonsterAttribute {
+        match self.attribute {
+            MonsterAttribute::Vampyre(_) => MonsterAttribute::Vampyre(0),
+            attribute => attribute,
+        }
+    }
+
+    pub fn description(&self) -> String {
+        let percent = |(num, den): (u32, u32)| (num as f64 / den as f64 - 1.0) * 100.0;
+        match (self.accuracy == NONE, self.damage == NONE) {
+            (true, true) => "Can harm the target".to_string(),
+            (false, false) if self.accuracy == self.damage => {
+                format!("+{:.1}% accuracy and damage", percent(self.accuracy))
+            }
+            (false, false) => format!(
+                "+{:.1}% accuracy, +{:.1}% damage",
+                percent(self.accuracy),
+                percent(self.damage)
+            ),
+            (true, _) => format!("+{:.1}% damage", percent(self.damage)),
+            (_, true) => format!("+{:.1}% accuracy", percent(self.accuracy)),
+        }
+    }
+}
+
+/// How an equipped item with an attribute effect fares against the target.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EffectCheck {
+    Applies {
+        item: String,
+        effect: GearEffect,
+    },
+    /// The target has the attribute, but the item doesn't boost this style.
+    WrongStyle {
+        item: String,
+        attribute: MonsterAttribute,
+    },
+    /// The target doesn't have the attribute the item is for.
+    WrongTarget {
+        item: String,
+        attribute: MonsterAttribute,
+    },
+    /// The target is a vampyre that only special weapons can harm.
+    CannotHarm {
+        attribute: MonsterAttribute,
+    },
+}
+
+/// Vampyres from this tier up only take damage from the weapons listed for
+/// their tier.
+const PROTECTED_VAMPYRE_TIER: u8 = 2;
+
+/// Checks every equipped item that has an attribute effect against the target.
+pub fn check_gear_effects(
+    player: &Player,
+    monster: &MonsterJson,
+    class: CombatClass,
+) -> Vec<EffectCheck> {
+    let mut checks = Vec::new();
+    for item in equipped_names(player) {
+        // An exact name beats a variant match, e.g. "Salve amulet (e)" isn't
+        // treated as a variant of "Salve amulet"
+        let exact: Vec<GearEffect> = GEAR_EFFECTS
+            .iter()
+            .filter(|effect| effect.items.contains(&item.as_str()))
+            .copied()
+            .collect();
+        let effects = if exact.is_empty() {
+            GEAR_EFFECTS
+                .iter()
+                .filter(|effect| effect.matches_item(&item))
+                .copied()
+                .collect()
+        } else {
+            exact
+        };
+        let Some(attribute) = effects.first().map(|effect| effect.target_attribute()) else {
+            continue;
+        };
+
+        let on_target: Vec<GearEffect> = effects
+            .into_iter()
+            .filter(|effect| effect.matches_target(monster))
+            .collect();
+        let check = match on_target
+            .iter()
+            .find(|effect| effect.styles.contains(&class))
+        {
+            Some(&effect) => EffectCheck::Applies { item, effect },
+            None if !on_target.is_empty() => EffectCheck::WrongStyle { item, attribute },
+            None => EffectCheck::WrongTarget { item, attribute },
+        };
+        checks.push(check);
+    }
+
+    let protected_tier = monster
+        .attributes()
+        .iter()
+        .find_map(|attribute| match attribute {
+            MonsterAttribute::Vampyre(tier) if *tier >= PROTECTED_VAMPYRE_TIER => Some(*tier),
+            _ => None,
+        });
+    let harms_vampyres = checks.iter().any(|check| {
+        matches!(
+            check,
+            EffectCheck::Applies { effect, .. }
+                if matches!(effect.attribute, MonsterAttribute::Vampyre(_))
+        )
+    });
+    if let Some(tier) = protected_tier.filter(|_| !harms_vampyres) {
+        checks.push(EffectCheck::CannotHarm {
+            attribute: MonsterAttribute::Vampyre(tier),
+        });
+    }
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn effect(item: &str) -> GearEffect {
+        *GEAR_EFFECTS
+            .iter()
+            .find(|effect| effect.items.contains(&item))
+            .expect("item has an effect")
+    }
+
+    fn monster_with(attribute: &str) -> MonsterJson {
+        let mut monster = MonsterJson::blank(-1);
+        monster.info.attributes = Some(vec![attribute.to_string()]);
+        monster
+    }
+
+    #[test]
+    fn descriptions_match_the_wiki() {
+        assert_eq!(
+            effect("Salve amulet(ei)").description(),
+            "+20.0% accuracy and damage"
+        );
+        assert_eq!(
+            effect("Arclight").description(),
+            "+70.0% accuracy and damage"
+        );
+        assert_eq!(
+            effect("Dragon hunter crossbow").description(),
+            "+30.0% accuracy, +25.0% damage"
+        );
+        assert_eq!(effect("Barronite mace").description(), "+15.0% damage");
+        assert_eq!(
+            effect("Rod of ivandis").description(),
+            "Can harm the target"
+        );
+    }
+
+    #[test]
+    fn variants_share_the_effect() {
+        assert!(effect("Arclight").matches_item("Arclight"));
+        assert!(effect("Keris partisan").matches_item("Keris partisan (Locked)"));
+        assert!(!effect("Salve amulet").matches_item("Salve amulet(ei)"));
+    }
+
+    #[test]
+    fn vampyre_tier_gates_the_weapons() {
+        let tier_2 = monster_with("vampyre2");
+        let tier_3 = monster_with("vampyre3");
+        assert!(effect("Silver sickle (b)").matches_target(&tier_2));
+        assert!(!effect("Silver sickle (b)").matches_target(&tier_3));
+        assert!(effect("Ivandis flail").matches_target(&tier_3));
+        assert!(effect("Blisterwood flail").matches_target(&monster_with("vampyre1")));
+    }
+
+    #[test]
+    fn other_attributes_must_match() {
+        assert!(effect("Bone mace").matches_target(&monster_with("rat")));
+        assert!(!effect("Gadderhammer").matches_target(&monster_with("undead")));
+    }
+}
@@ -0,0 +1,148 @@
+//! Server-side hiscore fetching with a shared HTTP client and an in-memory
+//! cache, so repeated lookups of the same name don't all reach Jagex.
+
+use crate::hiscores::{GameMode, HiscoreError, HiscoreLookup, JAGEX_BASE_URL};
+use async_std::sync::Mutex as AsyncMutex;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+/// Environment variable overriding the hiscore host, e.g. to point at the
+/// `hiscore_standin` server during offline development.
+const BASE_URL_ENV: &str = "HISCORE_BASE_URL";
+/// Environment variable overriding how long lookups are cached, in seconds.
+const CACHE_TTL_ENV: &str = "HISCORE_CACHE_TTL_SECS";
+const DEFAULT_CACHE_TTL_SECS: u64 = 300;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .expect("failed to build hiscore HTTP client")
+});
+
+static BASE_URL: LazyLock<String> = LazyLock::new(|| {
+    std::env::var(BASE_URL_ENV)
+        .map(|url| url.trim_end_matches('/').to_string())
+        .unwrap_or_else(|_| JAGEX_BASE_URL.to_string())
+});
+
+static CACHE_TTL: LazyLock<Duration> = LazyLock::new(|| {
+    let secs = std::env::var(CACHE_TTL_ENV)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_CACHE_TTL_SECS);
+    Duration::from_secs(secs)
+});
+
+struct CacheEntry {
+    data: String,
+    fetched_at: Instant,
+}
+
+type CacheKey = (String, GameMode);
+type CacheSlot = Arc<AsyncMutex<Option<CacheEntry>>>;
+
+// One slot per name and mode. Callers hold the slot's lock while fetching, so
+// concurrent lookups of the same name wait for and reuse a single request.
+static CACHE: LazyLock<Mutex<HashMap<CacheKey, CacheSlot>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// Jagex treats case, spaces, underscores and hyphens in names as equivalent
+fn cache_key(rsn: &str, mode: GameMode) -> CacheKey {
+    let name = rsn.trim().to_lowercase().replace(['_', '-'], " ");
+    (name, mode)
+}
+
+// Whether a slot can be dropped: nobody is using it and it holds nothing
+// fresh, e.g. an expired entry or a lookup that failed
+fn is_stale(slot: &CacheSlot) -> bool {
+    if Arc::strong_count(slot) > 1 {
+        return false;
+    }
+    match slot.try_lock() {
+        Some(entry) => entry
+            .as_ref()
+            .is_none_or(|cached| cached.fetched_at.elapsed() >= *CACHE_TTL),
+        None => false,
+    }
+}
+
+/// Returns the player's hiscore data, from the cache when it is fresh enough.
+pub async fn fetch(rsn: &str, mode: GameMode) -> Result<HiscoreLookup, HiscoreError> {
+    let slot = {
+        let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        let key = cache_key(rsn, mode);
+        if !cache.contains_key(&key) {
+            // Prune before adding, so unique names don't pile up forever
+            cache.retain(|_, slot| !is_stale(slot));
+        }
+        cache.entry(key).or_default().clone()
+    };
+
+    let mut entry = slot.lock().await;
+    if let Some(cached) = entry.as_ref() {
+        let age = cached.fetched_at.elapsed();
+        if age < *CACHE_TTL {
+            return Ok(HiscoreLookup {
+                data: cached.data.clone(),
+                age_secs: age.as_secs(),
+            });
+        }
+    }
+
+    let data = fetch_upstream(rsn, mode).await?;
+    *entry = Some(CacheEntry {
+        data: data.clone(),
+        fetched_at: Instant::now(),
+    });
+    Ok(HiscoreLookup { data, age_secs: 0 })
+}
+
+async fn fetch_upstream(rsn: &str, mode: GameMode) -> Result<String, HiscoreError> {
+    let url = format!("{}/m={}/index_lite.ws", *BASE_URL, mode.table());
+    let params = [("player", rsn)];
+
+    let response = CLIENT
+        .get(url)
+        .query(&params)
+        .send()
+        .await
+        .map_err(HiscoreError::from)?;
+    if let Some(error) = HiscoreError::from_status(response.status().as_u16()) {
+        return Err(error);
+    }
+    response.text().await.map_err(HiscoreError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_keyed_like_jagex_does() {
+        assert_eq!(
+            cache_key(" Zezima_Fan-1 ", GameMode::Regular),
+            cache_key("zezima fan 1", GameMode::Regular),
+        );
+    }
+
+    #[test]
+    fn empty_and_expired_slots_are_stale() {
+        let empty: CacheSlot = Arc::default();
+        assert!(is_stale(&empty));
+
+        let fresh: CacheSlot = Arc::new(AsyncMutex::new(Some(CacheEntry {
+            data: String::new(),
+            fetched_at: Instant::now(),
+        })));
+        assert!(!is_stale(&fresh));
+
+        let in_use = fresh.clone();
+        *in_use.try_lock().unwrap() = None;
+        assert!(!is_stale(&fresh));
+        drop(in_use);
+        assert!(is_stale(&fresh));
+    }
+}
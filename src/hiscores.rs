@@ -0,0 +1,676 @@
+use crate::skill::{level_for_xp, parse_level_input, LevelInput, Skill, ALL_SKILLS};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+/// A skill row from an `index_lite` hiscore response.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkillEntry {
+    pub skill: Skill,
+    pub level: u32,
+    /// Total experience, or `None` when the player is unranked in the skill.
+    pub xp: Option<u64>,
+}
+
+/// Reasons a hiscore lookup can fail.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum HiscoreError {
+    /// The hiscores returned 404 for the name.
+    NotFound,
+    /// The hiscores returned 429.
+    RateLimited,
+    /// The request to the hiscores timed out.
+    Timeout,
+    /// The hiscores or the app server couldn't be reached, or returned an
+    /// unexpected status.
+    Unavailable(String),
+    /// The response didn't have the expected format. `line` is 1-based.
+    Parse {
+        line: Option<usize>,
+        content: String,
+    },
+}
+
+impl HiscoreError {
+    /// The error for a non-success HTTP status from the hiscores.
+    pub fn from_status(status: u16) -> Option<Self> {
+        match status {
+            200..=299 => None,
+            404 => Some(HiscoreError::NotFound),
+            429 => Some(HiscoreError::RateLimited),
+            status => Some(HiscoreError::Unavailable(format!("HTTP {status}"))),
+        }
+    }
+
+    /// Whether retrying the same lookup might succeed.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            HiscoreError::RateLimited | HiscoreError::Timeout | HiscoreError::Unavailable(_)
+        )
+    }
+}
+
+impl fmt::Display for HiscoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HiscoreError::NotFound => write!(f, "Player not found on the selected hiscores"),
+            HiscoreError::RateLimited => {
+                write!(
+                    f,
+                    "The hiscores are rate limiting lookups, try again shortly"
+                )
+            }
+            HiscoreError::Timeout => write!(f, "The hiscores took too long to respond"),
+            HiscoreError::Unavailable(reason) => {
+                write!(f, "The hiscores are unavailable right now ({reason})")
+            }
+            HiscoreError::Parse {
+                line: Some(line),
+                content,
+            } => write!(f, "Unexpected hiscore data on line {line}: '{content}'"),
+            HiscoreError::Parse {
+                line: None,
+                content,
+            } => write!(f, "Unexpected hiscore data: {content}"),
+        }
+    }
+}
+
+impl std::error::Error for HiscoreError {}
+
+impl From<reqwest::Error> for HiscoreError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            HiscoreError::Timeout
+        } else {
+            HiscoreError::Unavailable(e.to_string())
+        }
+    }
+}
+
+/// Parses the skill rows of an `index_lite` response.
+///
+/// The first row is the overall total and is skipped. Unranked skills are
+/// reported at their starting level.
+pub fn parse_skill_entries(data: &str) -> Result<Vec<SkillEntry>, HiscoreError> {
+    let mut lines = data.lines().enumerate().skip(1);
+    ALL_SKILLS
+        .iter()
+        .map(|&skill| {
+            let (idx, line) = lines.next().ok_or_else(|| HiscoreError::Parse {
+                line: None,
+                content: format!("missing row for {}", skill.name()),
+            })?;
+            let mut fields = line.trim().split(',').skip(1);
+            let level = fields
+                .next()
+                .and_then(|field| field.parse::<i64>().ok())
+                .ok_or_else(|| HiscoreError::Parse {
+                    line: Some(idx + 1),
+                    content: line.to_string(),
+                })?;
+            let xp = fields
+                .next()
+                .and_then(|field| field.parse::<i64>().ok())
+                .filter(|&xp| xp >= 0)
+                .map(|xp| xp as u64);
+            Ok(SkillEntry {
+                skill,
+                level: (level.max(0) as u32).max(skill.min_level()),
+                xp,
+            })
+        })
+        .collect()
+}
+
+/// Names of the rows after the skills in an `index_lite` response, in order.
+/// Jagex adds rows when new content is released, so this has to follow the
+/// live hiscores.
+pub const ACTIVITIES: [&str; 86] = [
+    "League Points",
+    "Deadman Points",
+    "Bounty Hunter - Hunter",
+    "Bounty Hunter - Rogue",
+    "Bounty Hunter (Legacy) - Hunter",
+    "Bounty Hunter (Legacy) - Rogue",
+    "Clue Scrolls (all)",
+    "Clue Scrolls (beginner)",
+    "Clue Scrolls (easy)",
+    "Clue Scrolls (medium)",
+    "Clue Scrolls (hard)",
+    "Clue Scrolls (elite)",
+    "Clue Scrolls (master)",
+    "LMS - Rank",
+    "PvP Arena - Rank",
+    "Soul Wars Zeal",
+    "Rifts closed",
+    "Colosseum Glory",
+    "Collections Logged",
+    "Abyssal Sire",
+    "Alchemical Hydra",
+    "Amoxliatl",
+    "Araxxor",
+    "Artio",
+    "Barrows Chests",
+    "Bryophyta",
+    "Callisto",
+    "Calvar'ion",
+    "Cerberus",
+    "Chambers of Xeric",
+    "Chambers of Xeric: Challenge Mode",
+    "Chaos Elemental",
+    "Chaos Fanatic",
+    "Commander Zilyana",
+    "Corporeal Beast",
+    "Crazy Archaeologist",
+    "Dagannoth Prime",
+    "Dagannoth Rex",
+    "Dagannoth Supreme",
+    "Deranged Archaeologist",
+    "Doom of Mokhaiotl",
+    "Duke Sucellus",
+    "General Graardor",
+    "Giant Mole",
+    "Grotesque Guardians",
+    "Hespori",
+    "Kalphite Queen",
+    "King Black Dragon",
+    "Kraken",
+    "Kree'Arra",
+    "K'ril Tsutsaroth",
+    "Lunar Chests",
+    "Mimic",
+    "Nex",
+    "Nightmare",
+    "Phosani's Nightmare",
+    "Obor",
+    "Phantom Muspah",
+    "Sarachnis",
+    "Scorpia",
+    "Scurrius",
+    "Skotizo",
+    "Sol Heredit",
+    "Spindel",
+    "Tempoross",
+    "The Gauntlet",
+    "The Corrupted Gauntlet",
+    "The Hueycoatl",
+    "The Leviathan",
+    "The Royal Titans",
+    "The Whisperer",
+    "Theatre of Blood",
+    "Theatre of Blood: Hard Mode",
+    "Thermonuclear Smoke Devil",
+    "Tombs of Amascut",
+    "Tombs of Amascut: Expert Mode",
+    "TzKal-Zuk",
+    "TzTok-Jad",
+    "Vardorvis",
+    "Venenatis",
+    "Vet'ion",
+    "Vorkath",
+    "Wintertodt",
+    "Yama",
+    "Zalcano",
+    "Zulrah",
+];
+
+// Rows of ACTIVITIES holding clue scroll counts, and the first boss row
+const CLUE_ROWS: std::ops::Range<usize> = 6..13;
+const FIRST_BOSS_ROW: usize = 19;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ActivityKind {
+    Activity,
+    Clue,
+    Boss,
+}
+
+/// A ranked clue, activity or boss kill-count row from an `index_lite`
+/// response.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActivityEntry {
+    pub name: &'static str,
+    pub kind: ActivityKind,
+    pub rank: u64,
+    /// Completions, kill count or points.
+    pub score: u64,
+}
+
+/// Parses the rows following the skills in an `index_lite` response.
+///
+/// Unranked rows are left out, as are rows that don't parse, so a response
+/// with newer or missing rows still yields the ones that can be read.
+pub fn parse_activity_entries(data: &str) -> Vec<ActivityEntry> {
+    data.lines()
+        .skip(1 + ALL_SKILLS.len())
+        .zip(ACTIVITIES.iter().enumerate())
+        .filter_map(|(line, (idx, &name))| {
+            let mut fields = line.trim().split(',');
+            let rank = fields.next()?.parse::<i64>().ok()?;
+            let score = fields.next()?.parse::<i64>().ok()?;
+            let kind = if CLUE_ROWS.contains(&idx) {
+                ActivityKind::Clue
+            } else if idx >= FIRST_BOSS_ROW {
+                ActivityKind::Boss
+            } else {
+                ActivityKind::Activity
+            };
+            (rank > 0 && score >= 0).then_some(ActivityEntry {
+                name,
+                kind,
+                rank: rank as u64,
+                score: score as u64,
+            })
+        })
+        .collect()
+}
+
+/// Hiscore tables for the different account types and game modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, EnumIter)]
+pub enum GameMode {
+    #[default]
+    Regular,
+    Ironman,
+    HardcoreIronman,
+    UltimateIronman,
+    Leagues,
+    Deadman,
+    FreshStartWorld,
+    Tournament,
+    OneDefence,
+    Skiller,
+}
+
+impl GameMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Regular => "Regular",
+            GameMode::Ironman => "Ironman",
+            GameMode::HardcoreIronman => "Hardcore Ironman",
+            GameMode::UltimateIronman => "Ultimate Ironman",
+            GameMode::Leagues => "Leagues",
+            GameMode::Deadman => "Deadman",
+            GameMode::FreshStartWorld => "Fresh Start World",
+            GameMode::Tournament => "Tournament",
+            GameMode::OneDefence => "1 Defence Pure",
+            GameMode::Skiller => "Skiller",
+        }
+    }
+
+    /// Name of the hiscore table in the Jagex URL.
+    pub fn table(&self) -> &'static str {
+        match self {
+            GameMode::Regular => "hiscore_oldschool",
+            GameMode::Ironman => "hiscore_oldschool_ironman",
+            GameMode::HardcoreIronman => "hiscore_oldschool_hardcore_ironman",
+            GameMode::UltimateIronman => "hiscore_oldschool_ultimate",
+            GameMode::Leagues => "hiscore_oldschool_seasonal",
+            GameMode::Deadman => "hiscore_oldschool_deadman",
+            GameMode::FreshStartWorld => "hiscore_oldschool_fresh_start",
+            GameMode::Tournament => "hiscore_oldschool_tournament",
+            GameMode::OneDefence => "hiscore_oldschool_skiller_defence",
+            GameMode::Skiller => "hiscore_oldschool_skiller",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        GameMode::iter().find(|mode| mode.name() == name)
+    }
+}
+
+/// Order in which "try all modes" searches the tables. Ironman tables come
+/// first because ironmen also appear on the regular hiscores.
+pub const FALLBACK_ORDER: [GameMode; 10] = [
+    GameMode::UltimateIronman,
+    GameMode::HardcoreIronman,
+    GameMode::Ironman,
+    GameMode::Regular,
+    GameMode::Leagues,
+    GameMode::Deadman,
+    GameMode::FreshStartWorld,
+    GameMode::Tournament,
+    GameMode::OneDefence,
+    GameMode::Skiller,
+];
+
+/// Raw hiscore data with the age of the copy it came from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HiscoreLookup {
+    pub data: String,
+    /// Seconds since the data was fetched from the hiscores.
+    pub age_secs: u64,
+}
+
+/// Host of the official hiscores.
+pub const JAGEX_BASE_URL: &str = "https://secure.runescape.com";
+
+/// Fetches `index_lite` data from the browser through a CORS proxy, for builds
+/// hosted without the app server. The encoded hiscore URL is appended to
+/// `proxy`, e.g. `https://corsproxy.io/?url=`.
+pub async fn fetch_via_proxy(
+    proxy: &str,
+    rsn: &str,
+    mode: GameMode,
+) -> Result<String, HiscoreError> {
+    let target = format!(
+        "{JAGEX_BASE_URL}/m={}/index_lite.ws?player={}",
+        mode.table(),
+        percent_encode(rsn.trim())
+    );
+    let url = format!("{}{}", proxy.trim(), percent_encode(&target));
+
+    let response = reqwest::get(url).await.map_err(HiscoreError::from)?;
+    if let Some(error) = HiscoreError::from_status(response.status().as_u16()) {
+        return Err(error);
+    }
+    response.text().await.map_err(HiscoreError::from)
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// Converts pasted stats into `index_lite` text.
+///
+/// Accepts a raw `index_lite` response, the JSON hiscore format
+/// (`index_lite.json`), or a RuneLite-style skill export of `Skill: value`
+/// lines or a `{"Skill": value}` object. Export values follow the skills grid:
+/// up to 99 is a level, anything larger (or with a `k`/`m` suffix) is XP.
+/// Skills missing from an export are left at their starting level.
+pub fn normalize_stats_text(input: &str) -> Result<String, HiscoreError> {
+    let input = input.trim();
+    if input.starts_with('{') {
+        let json: Value = serde_json::from_str(input).map_err(|e| HiscoreError::Parse {
+            line: Some(e.line()),
+            content: e.to_string(),
+        })?;
+        return match json.get("skills") {
+            Some(Value::Array(skills)) => Ok(from_hiscore_json(skills, json.get("activities"))),
+            Some(skills @ Value::Object(_)) => from_skill_export(json_export_values(skills)?),
+            _ => from_skill_export(json_export_values(&json)?),
+        };
+    }
+
+    let starts_with_name = input
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic());
+    if starts_with_name {
+        return from_skill_export(line_export_values(input)?);
+    }
+
+    // Validate the skill rows so mistakes are reported against the pasted text
+    parse_skill_entries(input)?;
+    Ok(input.to_string())
+}
+
+fn json_number(value: Option<&Value>) -> i64 {
+    value.and_then(Value::as_i64).unwrap_or(-1)
+}
+
+// Rebuilds the rows of the JSON hiscore format in `index_lite` order
+fn from_hiscore_json(skills: &[Value], activities: Option<&Value>) -> String {
+    let row = |name: &str| {
+        skills
+            .iter()
+            .find(|skill| {
+                skill
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .is_some_and(|n| n.eq_ignore_ascii_case(name))
+            })
+            .map(|skill| {
+                format!(
+                    "{},{},{}",
+                    json_number(skill.get("rank")),
+                    json_number(skill.get("level")),
+                    json_number(skill.get("xp"))
+                )
+            })
+    };
+
+    let mut lines = vec![row("Overall").unwrap_or_else(|| "-1,-1,-1".to_string())];
+    for skill in ALL_SKILLS {
+        lines.push(row(skill.name()).unwrap_or_else(|| format!("-1,{},-1", skill.min_level())));
+    }
+    if let Some(Value::Array(activities)) = activities {
+        lines.extend(activities.iter().map(|activity| {
+            format!(
+                "{},{}",
+                json_number(activity.get("rank")),
+                json_number(activity.get("score"))
+            )
+        }));
+    }
+    lines.join("\n")
+}
+
+fn export_skill(
+    name: &str,
+    value: &str,
+    line: Option<usize>,
+) -> Result<Option<(Skill, LevelInput)>, HiscoreError> {
+    let name = name.trim().trim_matches('"');
+    if name.eq_ignore_ascii_case("overall") || name.eq_ignore_ascii_case("total") {
+        return Ok(None);
+    }
+    let parse_error = || HiscoreError::Parse {
+        line,
+        content: format!("{name}: {}", value.trim()),
+    };
+    let skill = ALL_SKILLS
+        .iter()
+        .find(|skill| skill.name().eq_ignore_ascii_case(name))
+        .ok_or_else(parse_error)?;
+    let value = parse_level_input(value.trim().trim_matches('"')).ok_or_else(parse_error)?;
+    Ok(Some((*skill, value)))
+}
+
+fn line_export_values(input: &str) -> Result<Vec<(Skill, LevelInput)>, HiscoreError> {
+    let mut values = Vec::new();
+    for (idx, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Some((name, value)) = line.split_once([':', '\t', ',']) else {
+            return Err(HiscoreError::Parse {
+                line: Some(idx + 1),
+                content: line.to_string(),
+            });
+        };
+        values.extend(export_skill(name, value, Some(idx + 1))?);
+    }
+    Ok(values)
+}
+
+fn json_export_values(json: &Value) -> Result<Vec<(Skill, LevelInput)>, HiscoreError> {
+    let Value::Object(entries) = json else {
+        return Err(HiscoreError::Parse {
+            line: None,
+            content: "expected an object of skill values".to_string(),
+        });
+    };
+    let mut values = Vec::new();
+    for (name, value) in entries {
+        let value = match value {
+            Value::String(value) => value.clone(),
+            value => value.to_string(),
+        };
+        values.extend(export_skill(name, &value, None)?);
+    }
+    Ok(values)
+}
+
+fn from_skill_export(values: Vec<(Skill, LevelInput)>) -> Result<String, HiscoreError> {
+    if values.is_empty() {
+        return Err(HiscoreError::Parse {
+            line: None,
+            content: "no skills found".to_string(),
+        });
+    }
+
+    let rows: Vec<(u32, Option<u64>)> = ALL_SKILLS
+        .iter()
+        .map(|skill| {
+            // Later lines win if a skill is listed twice
+            let value = values.iter().rev().find(|(s, _)| s == skill);
+            match value.map(|(_, value)| *value) {
+                Some(LevelInput::Level(level)) => (level.clamp(skill.min_level(), 99), None),
+                Some(LevelInput::Xp(xp)) => (level_for_xp(xp).max(skill.min_level()), Some(xp)),
+                None => (skill.min_level(), None),
+            }
+        })
+        .collect();
+
+    let total_level: u32 = rows.iter().map(|(level, _)| level).sum();
+    let mut lines = vec![format!("-1,{total_level},-1")];
+    lines.extend(rows.iter().map(|(level, xp)| match xp {
+        Some(xp) => format!("-1,{level},{xp}"),
+        None => format!("-1,{level},-1"),
+    }));
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An `index_lite` response with every skill at `level`, then `activities`.
+    fn index_lite(level: &str, activities: &[&str]) -> String {
+        let mut lines = vec!["1,2277,4600000000".to_string()];
+        lines.extend(ALL_SKILLS.iter().map(|_| format!("1,{level}")));
+        lines.extend(activities.iter().map(|row| row.to_string()));
+        lines.join("\n")
+    }
+
+    #[test]
+    fn parses_skill_rows() {
+        let entries = parse_skill_entries(&index_lite("99,13034431", &[])).unwrap();
+        assert_eq!(entries.len(), ALL_SKILLS.len());
+        assert_eq!(
+            entries[3],
+            SkillEntry {
+                skill: Skill::Hitpoints,
+                level: 99,
+                xp: Some(13_034_431),
+            }
+        );
+
+        // Unranked skills start at their minimum level, with no experience
+        let unranked = parse_skill_entries(&index_lite("-1,-1", &[])).unwrap();
+        assert_eq!(unranked[0].level, 1);
+        assert_eq!(unranked[3].level, 10);
+        assert_eq!(unranked[3].xp, None);
+    }
+
+    #[test]
+    fn reports_bad_rows() {
+        let text = index_lite("99,13034431", &[]).replacen("1,99,13034431", "1,abc,0", 1);
+        assert_eq!(
+            parse_skill_entries(&text),
+            Err(HiscoreError::Parse {
+                line: Some(2),
+                content: "1,abc,0".to_string(),
+            })
+        );
+        assert!(matches!(
+            parse_skill_entries("1,2277,0\n1,99,0"),
+            Err(HiscoreError::Parse { line: None, .. })
+        ));
+    }
+
+    #[test]
+    fn status_errors() {
+        assert_eq!(HiscoreError::from_status(200), None);
+        assert_eq!(HiscoreError::from_status(404), Some(HiscoreError::NotFound));
+        assert_eq!(
+            HiscoreError::from_status(429),
+            Some(HiscoreError::RateLimited)
+        );
+        assert!(!HiscoreError::NotFound.is_retryable());
+        assert!(HiscoreError::from_status(503).unwrap().is_retryable());
+    }
+
+    #[test]
+    fn normalizes_skill_exports() {
+        let text = normalize_stats_text("Attack: 99\nHitpoints: 13.03m\nTotal: 2277").unwrap();
+        let entries = parse_skill_entries(&text).unwrap();
+        assert_eq!(entries[0].level, 99);
+        assert_eq!(entries[0].xp, None);
+        assert_eq!(entries[3].level, 98);
+        assert_eq!(entries[3].xp, Some(13_030_000));
+        // Skills left out start at their minimum level
+        assert_eq!(entries[1].level, 1);
+
+        let json = normalize_stats_text(r#"{"Strength": "1.5m", "Prayer": 70}"#).unwrap();
+        let entries = parse_skill_entries(&json).unwrap();
+        assert_eq!(entries[2].level, level_for_xp(1_500_000));
+        assert_eq!(entries[5].level, 70);
+
+        assert!(matches!(
+            normalize_stats_text("Attack 99"),
+            Err(HiscoreError::Parse { line: Some(1), .. })
+        ));
+        assert!(normalize_stats_text("Sailing: 99").is_err());
+    }
+
+    #[test]
+    fn normalizes_hiscore_json() {
+        let json = r#"{
+            "skills": [
+                {"name": "Overall", "rank": 5, "level": 1500, "xp": 1000000},
+                {"name": "Magic", "rank": 9, "level": 94, "xp": 8000000}
+            ]
+        }"#;
+        let entries = parse_skill_entries(&normalize_stats_text(json).unwrap()).unwrap();
+        assert_eq!(entries[6].level, 94);
+        assert_eq!(entries[6].xp, Some(8_000_000));
+        assert_eq!(entries[3].level, 10);
+    }
+
+    #[test]
+    fn parses_activity_rows() {
+        let mut rows = vec!["-1,-1"; FIRST_BOSS_ROW + 2];
+        rows[1] = "12,4000";
+        rows[6] = "800,152";
+        rows[7] = "oops";
+        rows[FIRST_BOSS_ROW] = "3000,250";
+        let entries = parse_activity_entries(&index_lite("99,13034431", &rows));
+        assert_eq!(
+            entries,
+            [
+                ActivityEntry {
+                    name: "Deadman Points",
+                    kind: ActivityKind::Activity,
+                    rank: 12,
+                    score: 4000,
+                },
+                ActivityEntry {
+                    name: "Clue Scrolls (all)",
+                    kind: ActivityKind::Clue,
+                    rank: 800,
+                    score: 152,
+                },
+                ActivityEntry {
+                    name: "Abyssal Sire",
+                    kind: ActivityKind::Boss,
+                    rank: 3000,
+                    score: 250,
+                },
+            ]
+        );
+    }
+}
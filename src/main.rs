@@ -1,10 +1,32 @@
-use components::{EquipmentGrid, EquipmentSelect, PotionSelect, PrayerSelect, SkillsSelect};
+use components::{
+    CombatResults, CoxContextPanel, CustomMonsterPanel, DefenceResults, DpsTable, EquipmentGrid,
+    EquipmentSelect, MonsterEditor, MonsterSelect, MonsterVersions, PotionSelect, PrayerSelect,
+    SkillsSelect, SpellSelect, ToaContextPanel, TripResults,
+};
 use dioxus::prelude::*;
 use dioxus_logger::tracing::Level;
 use state::AppState;
 
+mod boosts;
+mod calc;
 mod components;
+mod defence;
+mod dot;
+mod gear_effects;
+#[cfg(feature = "server")]
+mod hiscore_cache;
+mod hiscores;
+mod monster;
+mod profiles;
+mod raids;
+mod rates;
+mod skill;
+mod spells;
 mod state;
+mod storage;
+mod supplies;
+mod timers;
+mod unlocks;
 
 const TAILWIND_CSS: Asset = asset!("/assets/tailwind.css");
 
@@ -16,6 +38,7 @@ pub const PLACEHOLDERS_ASSETS: Asset = asset!("/assets/placeholders");
 pub const DEF_REDUCTIONS_ASSETS: Asset = asset!("/assets/def_reductions");
 pub const STYLES_ASSETS: Asset = asset!("/assets/styles");
 pub const BONUSES_ASSETS: Asset = asset!("/assets/bonuses");
+pub const SKILLS_ASSETS: Asset = asset!("/assets/skills");
 
 fn main() {
     dioxus_logger::init(Level::INFO).expect("failed to init logger");
@@ -24,7 +47,22 @@ fn main() {
 
 #[component]
 fn App() -> Element {
-    use_context_provider(|| Signal::new(AppState::default()));
+    let mut app_state = use_context_provider(|| Signal::new(AppState::default()));
+
+    // Saved data is only available on the client, so restore it after the
+    // first render
+    use_effect(move || {
+        let mut state = app_state.write();
+        state.profiles = profiles::ProfileStore::load();
+        state.custom_monsters = monster::load_custom_monsters();
+        if let (Some(profile), Some(snapshot)) =
+            (state.profiles.active, state.profiles.loaded_snapshot)
+        {
+            if let Err(e) = state.load_snapshot(profile, snapshot) {
+                log::warn!("Failed to restore saved stats: {e}");
+            }
+        }
+    });
 
     rsx! {
         document::Link { rel: "stylesheet", href: TAILWIND_CSS }
@@ -46,6 +84,27 @@ fn App() -> Element {
                     PotionSelect {}
                 }
             }
+            div {
+                class: "panel p-4 ml-6 w-96 self-start",
+                h1 {
+                    class: "text-xl font-bold mb-4 text-accent text-center",
+                    "Target"
+                }
+                MonsterSelect {}
+                MonsterVersions {}
+                CustomMonsterPanel {}
+                MonsterEditor {}
+                ToaContextPanel {}
+                CoxContextPanel {}
+                CombatResults {}
+                DefenceResults {}
+                TripResults {}
+                SpellSelect {}
+            }
+            div {
+                class: "panel p-4 ml-6 w-[36rem] self-start",
+                DpsTable {}
+            }
         }
     }
 }
@@ -0,0 +1,386 @@
+use crate::storage;
+use osrs::types::equipment::CombatType;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+const MONSTERS_JSON_STRING: &str = include_str!("../assets/json/monsters.json");
+const CUSTOM_MONSTERS_KEY: &str = "custom_monsters";
+
+/// A monster as stored in monsters.json.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonsterJson {
+    pub info: MonsterInfo,
+    pub stats: MonsterStats,
+    pub bonuses: MonsterBonuses,
+    pub immunities: MonsterImmunities,
+    pub max_hit: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonsterInfo {
+    pub id: i64,
+    pub name: String,
+    pub version: Option<String>,
+    pub combat_level: i32,
+    pub attack_speed: i32,
+    pub attack_styles: Option<Vec<String>>,
+    pub size: i32,
+    pub attributes: Option<Vec<String>>,
+    pub weakness: Option<MonsterWeakness>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonsterWeakness {
+    pub element: String,
+    pub severity: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MonsterStats {
+    pub attack: i32,
+    pub defence: i32,
+    pub hitpoints: i32,
+    pub magic: i32,
+    pub ranged: i32,
+    pub strength: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MonsterBonuses {
+    pub attack: MonsterAttackBonuses,
+    pub defence: MonsterDefenceBonuses,
+    pub strength: MonsterAttackBonuses,
+    pub flat_armour: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MonsterAttackBonuses {
+    pub melee: i32,
+    pub ranged: i32,
+    pub magic: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MonsterDefenceBonuses {
+    pub stab: i32,
+    pub slash: i32,
+    pub crush: i32,
+    pub magic: i32,
+    pub light: i32,
+    pub standard: i32,
+    pub heavy: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonsterImmunities {
+    pub poison: bool,
+    pub venom: bool,
+    pub freeze: i32,
+    pub burn: Option<String>,
+}
+
+/// Monster attributes that gear effects and mechanics depend on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MonsterAttribute {
+    Undead,
+    Demon,
+    Dragon,
+    Spectral,
+    Kalphite,
+    Fiery,
+    Xerician,
+    Golem,
+    Shade,
+    /// Vampyre tier 1 to 3, or 0 for vampyres of any tier.
+    Vampyre(u8),
+    Leafy,
+    Rat,
+    Penance,
+}
+
+impl MonsterAttribute {
+    /// Parses an attribute as written in monsters.json, e.g. "vampyre2".
+    pub fn from_json(name: &str) -> Option<Self> {
+        let attribute = match name {
+            "undead" => MonsterAttribute::Undead,
+            "demon" => MonsterAttribute::Demon,
+            "dragon" => MonsterAttribute::Dragon,
+            "spectral" => MonsterAttribute::Spectral,
+            "kalphite" => MonsterAttribute::Kalphite,
+            "fiery" => MonsterAttribute::Fiery,
+            "xerician" => MonsterAttribute::Xerician,
+            "golem" => MonsterAttribute::Golem,
+            "shade" => MonsterAttribute::Shade,
+            "leafy" => MonsterAttribute::Leafy,
+            "rat" => MonsterAttribute::Rat,
+            "penance" => MonsterAttribute::Penance,
+            _ => {
+                let tier = name.strip_prefix("vampyre")?.parse().ok()?;
+                MonsterAttribute::Vampyre(tier)
+            }
+        };
+        Some(attribute)
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            MonsterAttribute::Undead => "Undead".to_string(),
+            MonsterAttribute::Demon => "Demon".to_string(),
+            MonsterAttribute::Dragon => "Dragon".to_string(),
+            MonsterAttribute::Spectral => "Spectral".to_string(),
+            MonsterAttribute::Kalphite => "Kalphite".to_string(),
+            MonsterAttribute::Fiery => "Fiery".to_string(),
+            MonsterAttribute::Xerician => "Xerician".to_string(),
+            MonsterAttribute::Golem => "Golem".to_string(),
+            MonsterAttribute::Shade => "Shade".to_string(),
+            MonsterAttribute::Vampyre(0) => "Vampyre".to_string(),
+            MonsterAttribute::Vampyre(tier) => format!("Vampyre (tier {tier})"),
+            MonsterAttribute::Leafy => "Leafy".to_string(),
+            MonsterAttribute::Rat => "Rat".to_string(),
+            MonsterAttribute::Penance => "Penance".to_string(),
+        }
+    }
+}
+
+/// Strength of a burn, from the weakest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BurnStrength {
+    Weak,
+    Normal,
+    Strong,
+}
+
+impl BurnStrength {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BurnStrength::Weak => "weak",
+            BurnStrength::Normal => "normal",
+            BurnStrength::Strong => "strong",
+        }
+    }
+}
+
+impl MonsterImmunities {
+    /// The strongest burn the monster is immune to, normalised from the
+    /// free-text burn field.
+    pub fn burn_immunity(&self) -> Option<BurnStrength> {
+        let burn = self.burn.as_deref()?.to_lowercase();
+        if burn.contains("weak") {
+            Some(BurnStrength::Weak)
+        } else if burn.contains("normal") {
+            Some(BurnStrength::Normal)
+        } else if burn.starts_with("immune") {
+            // "Immune" and "Immune to strong burns" both block every burn
+            Some(BurnStrength::Strong)
+        } else {
+            None
+        }
+    }
+
+    pub fn blocks_burn(&self, strength: BurnStrength) -> bool {
+        self.burn_immunity()
+            .is_some_and(|immunity| strength <= immunity)
+    }
+}
+
+/// Every monster in the bundled monsters.json.
+pub static MONSTERS: LazyLock<Vec<MonsterJson>> = LazyLock::new(|| {
+    serde_json::from_str(MONSTERS_JSON_STRING).unwrap_or_else(|e| {
+        log::error!("Failed to parse monsters.json: {e}");
+        Vec::new()
+    })
+});
+
+/// User-made monsters saved on this device.
+pub fn load_custom_monsters() -> Vec<MonsterJson> {
+    storage::load(CUSTOM_MONSTERS_KEY).unwrap_or_default()
+}
+
+pub fn save_custom_monsters(monsters: &[MonsterJson]) {
+    storage::save(CUSTOM_MONSTERS_KEY, &monsters);
+}
+
+/// Every version of a monster sharing `name`, custom ones first, with numbered
+/// phases in fight order.
+pub fn versions_of(name: &str, custom_monsters: &[MonsterJson]) -> Vec<MonsterJson> {
+    let mut versions: Vec<MonsterJson> = custom_monsters
+        .iter()
+        .chain(MONSTERS.iter())
+        .filter(|monster| monster.info.name == name)
+        .cloned()
+        .collect();
+    // Stable, so versions without a phase keep their monsters.json order
+    versions.sort_by_key(|monster| monster.phase().map(|(_, phase)| phase));
+    versions
+}
+
+/// Parses a single monster or a list of monsters in the monsters.json format.
+pub fn parse_monsters(json: &str) -> Result<Vec<MonsterJson>, serde_json::Error> {
+    let json = json.trim();
+    if json.starts_with('[') {
+        serde_json::from_str(json)
+    } else {
+        serde_json::from_str(json).map(|monster| vec![monster])
+    }
+}
+
+impl MonsterJson {
+    /// A level 1 monster with no bonuses, as a starting point for custom ones.
+    pub fn blank(id: i64) -> Self {
+        let no_attack = MonsterAttackBonuses {
+            melee: 0,
+            ranged: 0,
+            magic: 0,
+        };
+        Self {
+            info: MonsterInfo {
+                id,
+                name: String::new(),
+                version: None,
+                combat_level: 1,
+                attack_speed: 4,
+                attack_styles: None,
+                size: 1,
+                attributes: None,
+                weakness: None,
+            },
+            stats: MonsterStats {
+                attack: 1,
+                defence: 1,
+                hitpoints: 10,
+                magic: 1,
+                ranged: 1,
+                strength: 1,
+            },
+            bonuses: MonsterBonuses {
+                attack: no_attack,
+                defence: MonsterDefenceBonuses {
+                    stab: 0,
+                    slash: 0,
+                    crush: 0,
+                    magic: 0,
+                    light: 0,
+                    standard: 0,
+                    heavy: 0,
+                },
+                strength: no_attack,
+                flat_armour: 0,
+            },
+            immunities: MonsterImmunities {
+                poison: false,
+                venom: false,
+                freeze: 0,
+                burn: None,
+            },
+            max_hit: None,
+        }
+    }
+
+    /// Attributes from monsters.json, skipping any this app doesn't know.
+    pub fn attributes(&self) -> Vec<MonsterAttribute> {
+        self.info
+            .attributes
+            .iter()
+            .flatten()
+            .filter_map(|name| MonsterAttribute::from_json(name))
+            .collect()
+    }
+
+    pub fn has_attribute(&self, attribute: MonsterAttribute) -> bool {
+        self.attributes().contains(&attribute)
+    }
+
+    /// Whether two entries are the same monster and version.
+    pub fn same_entry(&self, other: &MonsterJson) -> bool {
+        self.info.name == other.info.name && self.info.version == other.info.version
+    }
+
+    /// Name with the version in brackets, e.g. "Vorkath (Post-quest)".
+    pub fn display_name(&self) -> String {
+        match &self.info.version {
+            Some(version) => format!("{} ({version})", self.info.name),
+            None => self.info.name.clone(),
+        }
+    }
+
+    /// The mode and phase number of a multi-phase boss version, e.g.
+    /// "Hard mode, Phase 2" is ("Hard mode", 2).
+    pub fn phase(&self) -> Option<(String, u32)> {
+        let version = self.info.version.as_deref()?;
+        let start = version.find("Phase ")?;
+        let number = version[start + "Phase ".len()..]
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect::<String>()
+            .parse()
+            .ok()?;
+        let mode = version[..start].trim_end_matches([',', ' ']).to_string();
+        Some((mode, number))
+    }
+
+    /// Key that tells apart versions sharing an id.
+    pub fn key(&self) -> String {
+        format!(
+            "{}-{}-{}",
+            self.info.id,
+            self.info.name,
+            self.info.version.as_deref().unwrap_or("novariant")
+        )
+    }
+
+    pub fn defence_bonus(&self, combat_type: CombatType) -> i32 {
+        let defence = &self.bonuses.defence;
+        match combat_type {
+            CombatType::Stab => defence.stab,
+            CombatType::Slash => defence.slash,
+            CombatType::Crush => defence.crush,
+            CombatType::Light => defence.light,
+            CombatType::Standard => defence.standard,
+            CombatType::Heavy => defence.heavy,
+            CombatType::Magic => defence.magic,
+            _ => 0,
+        }
+    }
+
+    /// Defence roll against the given attack type. Magic attacks are defended
+    /// with the monster's Magic level rather than its Defence.
+    pub fn defence_roll(&self, combat_type: CombatType) -> u32 {
+        let level = match combat_type {
+            CombatType::Magic => self.stats.magic,
+            _ => self.stats.defence,
+        };
+        let bonus = self.defence_bonus(combat_type);
+        ((level + 9).max(0) * (bonus + 64).max(0)) as u32
+    }
+}
+
+/// The monster being fought: its wiki values, the values in use after any
+/// edits, and how much health it has left.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectedMonster {
+    pub wiki: MonsterJson,
+    pub edited: MonsterJson,
+    pub current_hp: u32,
+}
+
+impl SelectedMonster {
+    pub fn new(monster: MonsterJson) -> Self {
+        Self {
+            current_hp: monster.stats.hitpoints.max(0) as u32,
+            edited: monster.clone(),
+            wiki: monster,
+        }
+    }
+
+    pub fn is_edited(&self) -> bool {
+        self.edited != self.wiki || self.current_hp != self.max_hp()
+    }
+
+    pub fn max_hp(&self) -> u32 {
+        self.edited.stats.hitpoints.max(0) as u32
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.wiki.clone());
+    }
+}
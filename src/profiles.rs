@@ -0,0 +1,122 @@
+use crate::hiscores::GameMode;
+use crate::storage;
+use crate::unlocks::Unlock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const STORAGE_KEY: &str = "profiles";
+
+/// Stats of an account at one point in time, as `index_lite` data.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatSnapshot {
+    /// When the data was fetched from the hiscores, in seconds since the Unix
+    /// epoch.
+    pub taken_at: u64,
+    pub data: String,
+}
+
+/// A looked-up account and the stats recorded for it, oldest first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    /// The hiscore table the account was last found on.
+    pub mode: Option<GameMode>,
+    pub snapshots: Vec<StatSnapshot>,
+    /// Unlocks switched on or off by hand.
+    #[serde(default)]
+    pub unlocks: HashMap<Unlock, bool>,
+}
+
+impl Profile {
+    pub fn latest(&self) -> Option<&StatSnapshot> {
+        self.snapshots.last()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ProfileStore {
+    pub profiles: Vec<Profile>,
+    /// Index of the profile whose stats are loaded.
+    pub active: Option<usize>,
+    /// Index of the loaded snapshot within the active profile.
+    pub loaded_snapshot: Option<usize>,
+}
+
+// Hiscore names ignore case and treat spaces, underscores and hyphens alike
+fn same_name(a: &str, b: &str) -> bool {
+    let normalise = |name: &str| name.trim().to_lowercase().replace(['_', '-'], " ");
+    normalise(a) == normalise(b)
+}
+
+impl ProfileStore {
+    pub fn load() -> Self {
+        storage::load(STORAGE_KEY).unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        storage::save(STORAGE_KEY, self);
+    }
+
+    pub fn active_profile(&self) -> Option<&Profile> {
+        self.profiles.get(self.active?)
+    }
+
+    pub fn active_profile_mut(&mut self) -> Option<&mut Profile> {
+        self.profiles.get_mut(self.active?)
+    }
+
+    /// Records stats for an account, creating its profile if needed, and makes
+    /// it the active profile. Data identical to the latest snapshot isn't
+    /// stored again.
+    pub fn record(&mut self, name: &str, mode: Option<GameMode>, data: &str, taken_at: u64) {
+        let idx = match self
+            .profiles
+            .iter()
+            .position(|profile| same_name(&profile.name, name))
+        {
+            Some(idx) => idx,
+            None => {
+                self.profiles.push(Profile {
+                    name: name.trim().to_string(),
+                    mode,
+                    snapshots: Vec::new(),
+                    unlocks: HashMap::new(),
+                });
+                self.profiles.len() - 1
+            }
+        };
+
+        let profile = &mut self.profiles[idx];
+        if mode.is_some() {
+            profile.mode = mode;
+        }
+        if profile
+            .latest()
+            .is_none_or(|latest| latest.data.trim() != data.trim())
+        {
+            profile.snapshots.push(StatSnapshot {
+                taken_at,
+                data: data.to_string(),
+            });
+        }
+        self.active = Some(idx);
+        self.loaded_snapshot = Some(profile.snapshots.len() - 1);
+        self.save();
+    }
+
+    pub fn remove(&mut self, idx: usize) {
+        if idx >= self.profiles.len() {
+            return;
+        }
+        self.profiles.remove(idx);
+        self.active = match self.active {
+            Some(active) if active == idx => None,
+            Some(active) if active > idx => Some(active - 1),
+            active => active,
+        };
+        if self.active.is_none() {
+            self.loaded_snapshot = None;
+        }
+        self.save();
+    }
+}
@@ -0,0 +1,506 @@
+//! Raid scaling applied to the selected monster before any calculation.
+//!
+//! monsters.json lists raid monsters at their base stats. Tombs of Amascut
+//! monsters gain hitpoints with the raid level, the party size and, for the
+//! path bosses and their minions, the path level. Their Defence scales with
+//! the raid level alone, which comes from the active invocations.
+//!
+//! Chambers of Xeric monsters scale with the party size and the highest
+//! combat and Hitpoints levels in the party. In challenge mode they use their
+//! challenge mode entry, or gain 50% on every level where there isn't one.
+//! The Great Olm scales differently and is left at its listed stats.
+
+use crate::monster::{versions_of, MonsterAttribute, MonsterJson};
+
+/// Tombs of Amascut monsters that scale with the raid level and party size.
+const TOA_MONSTERS: [&str; 9] = [
+    "Baboon Brawler",
+    "Baboon Mage",
+    "Baboon Thrower",
+    "Baboon Shaman",
+    "Cursed Baboon",
+    "Volatile Baboon",
+    "Obelisk (Tombs of Amascut)",
+    "Tumeken's Warden",
+    "Elidinis' Warden",
+];
+
+/// Path bosses and their minions, which also scale with the path level.
+const TOA_PATH_MONSTERS: [&str; 12] = [
+    "Akkha",
+    "Akkha's Shadow",
+    "Ba-Ba",
+    "Kephri",
+    "Agile Scarab",
+    "Soldier Scarab",
+    "Spitting Scarab",
+    "Arcane Scarab",
+    "Scarab Swarm (Tombs of Amascut)",
+    "Scarab (Tombs of Amascut)",
+    "Zebak",
+    "Crocodile (Tombs of Amascut)",
+];
+
+pub const TOA_MAX_RAID_LEVEL: u32 = 600;
+pub const TOA_MAX_PATH_LEVEL: u32 = 6;
+pub const TOA_MAX_PARTY_SIZE: u32 = 8;
+
+/// An invocation and the raid levels it adds. Invocations sharing a group
+/// are tiers of the same challenge, so only one of them can be active.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Invocation {
+    pub name: &'static str,
+    pub raid_level: u32,
+    pub group: Option<&'static str>,
+}
+
+const fn invocation(
+    name: &'static str,
+    raid_level: u32,
+    group: Option<&'static str>,
+) -> Invocation {
+    Invocation {
+        name,
+        raid_level,
+        group,
+    }
+}
+
+const ATTEMPTS: Option<&str> = Some("Attempts");
+const TIME_LIMIT: Option<&str> = Some("Time limit");
+const HELPFUL_SPIRIT: Option<&str> = Some("Helpful spirit");
+const PATH: Option<&str> = Some("Path");
+
+pub const TOA_INVOCATIONS: [Invocation; 44] = [
+    invocation("Try Again", 5, ATTEMPTS),
+    invocation("Persistence", 10, ATTEMPTS),
+    invocation("Softcore Run", 15, ATTEMPTS),
+    invocation("Hardcore Run", 25, ATTEMPTS),
+    invocation("Walk for It", 10, TIME_LIMIT),
+    invocation("Jog for It", 15, TIME_LIMIT),
+    invocation("Run for It", 20, TIME_LIMIT),
+    invocation("Sprint for It", 25, TIME_LIMIT),
+    invocation("Need Some Help?", 15, HELPFUL_SPIRIT),
+    invocation("Need Less Help?", 25, HELPFUL_SPIRIT),
+    invocation("No Help Needed", 40, HELPFUL_SPIRIT),
+    invocation("Walk the Path", 50, None),
+    invocation("Pathseeker", 15, PATH),
+    invocation("Pathfinder", 40, PATH),
+    invocation("Pathmaster", 50, PATH),
+    invocation("Quiet Prayers", 20, None),
+    invocation("Deadly Prayers", 20, None),
+    invocation("On a Diet", 15, None),
+    invocation("Dehydration", 30, None),
+    invocation("Overly Draining", 15, None),
+    invocation("Lively Larvae", 5, None),
+    invocation("More Overlords", 15, None),
+    invocation("Blowing Mud", 10, None),
+    invocation("Medic!", 15, None),
+    invocation("Aerial Assault", 10, None),
+    invocation("Not Just a Head", 15, None),
+    invocation("Arterial Spray", 10, None),
+    invocation("Blood Thinners", 5, None),
+    invocation("Upset Stomach", 15, None),
+    invocation("Double Trouble", 20, None),
+    invocation("Keep Back", 10, None),
+    invocation("Stay Vigilant", 15, None),
+    invocation("Feeling Special?", 20, None),
+    invocation("Mind the Gap!", 10, None),
+    invocation("Gotta Have Faith", 10, None),
+    invocation("Jungle Japes", 5, None),
+    invocation("Shaking Things Up", 10, None),
+    invocation("Boulderdash", 10, None),
+    invocation("Ancient Haste", 10, None),
+    invocation("Acceleration", 10, None),
+    invocation("Penetration", 10, None),
+    invocation("Overclocked", 10, None),
+    invocation("Overclocked 2", 10, None),
+    invocation("Insanity", 50, None),
+];
+
+/// Settings of the Tombs of Amascut raid being planned.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToaContext {
+    pub enabled: bool,
+    /// Which of `TOA_INVOCATIONS` are active.
+    pub invocations: [bool; TOA_INVOCATIONS.len()],
+    /// Level of the path the monster is on, from 0.
+    pub path_level: u32,
+    pub party_size: u32,
+}
+
+impl Default for ToaContext {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            invocations: [false; TOA_INVOCATIONS.len()],
+            path_level: 0,
+            party_size: 1,
+        }
+    }
+}
+
+impl ToaContext {
+    /// Raid level from the active invocations.
+    pub fn raid_level(&self) -> u32 {
+        TOA_INVOCATIONS
+            .iter()
+            .zip(self.invocations)
+            .filter(|(_, active)| *active)
+            .map(|(invocation, _)| invocation.raid_level)
+            .sum::<u32>()
+            .min(TOA_MAX_RAID_LEVEL)
+    }
+
+    /// Switches an invocation on or off, switching off the other tiers of
+    /// its group.
+    pub fn set_invocation(&mut self, index: usize, active: bool) {
+        let group = TOA_INVOCATIONS[index].group;
+        if active && group.is_some() {
+            for (other, invocation) in TOA_INVOCATIONS.iter().enumerate() {
+                if invocation.group == group {
+                    self.invocations[other] = false;
+                }
+            }
+        }
+        self.invocations[index] = active;
+    }
+
+    pub fn applies_to(monster: &MonsterJson) -> bool {
+        let name = monster.info.name.as_str();
+        TOA_MONSTERS.contains(&name) || TOA_PATH_MONSTERS.contains(&name)
+    }
+
+    /// Multiplier on hitpoints for a monster, or 1 for one outside the raid.
+    pub fn hitpoints_factor(&self, monster: &MonsterJson) -> f64 {
+        if !self.enabled || !Self::applies_to(monster) {
+            return 1.0;
+        }
+        // +2% for every 5 raid levels
+        let raid = 1.0 + 0.004 * self.raid_level() as f64;
+        // +8% at path level 1, then +5% for each level after
+        let path_level = self.path_level.min(TOA_MAX_PATH_LEVEL);
+        let path = if TOA_PATH_MONSTERS.contains(&monster.info.name.as_str()) && path_level > 0 {
+            1.0 + 0.08 + 0.05 * (path_level - 1) as f64
+        } else {
+            1.0
+        };
+        // +90% for each of the second and third players, +60% for each after
+        let party_size = self.party_size.clamp(1, TOA_MAX_PARTY_SIZE);
+        let party =
+            1.0 + 0.9 * (party_size.min(3) - 1) as f64 + 0.6 * party_size.saturating_sub(3) as f64;
+        raid * path * party
+    }
+
+    /// Scales the monster's hitpoints and Defence for this raid.
+    pub fn apply(&self, monster: &mut MonsterJson) {
+        let factor = self.hitpoints_factor(monster);
+        if factor == 1.0 {
+            return;
+        }
+        let hitpoints = monster.stats.hitpoints as f64 * factor;
+        // Scaled hitpoints are rounded to a multiple of 10, or 5 below 100
+        let step = if hitpoints < 100.0 { 5.0 } else { 10.0 };
+        monster.stats.hitpoints = ((hitpoints / step).round() * step) as i32;
+
+        // Defence gains 2% for every 5 raid levels, rounded down
+        let defence = monster.stats.defence.max(0) as u32;
+        monster.stats.defence = (defence * (250 + self.raid_level()) / 250) as i32;
+    }
+}
+
+pub const COX_MAX_PARTY_SIZE: u32 = 100;
+/// Multiplier on every level in challenge mode for monsters without a
+/// challenge mode entry in monsters.json, as a fraction.
+const COX_CM_LEVELS: (u32, u32) = (3, 2);
+/// Monsters left out of the party scaling formula.
+const COX_UNSCALED: [&str; 1] = ["Great Olm"];
+
+/// Settings of the Chambers of Xeric raid being planned.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoxContext {
+    pub enabled: bool,
+    pub party_size: u32,
+    /// Highest combat level in the party, or `None` for the player's own.
+    pub max_combat: Option<u32>,
+    /// Highest Hitpoints level in the party, or `None` for the player's own.
+    pub max_hitpoints: Option<u32>,
+    pub challenge_mode: bool,
+}
+
+impl Default for CoxContext {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            party_size: 1,
+            max_combat: None,
+            max_hitpoints: None,
+            challenge_mode: false,
+        }
+    }
+}
+
+/// Whether a monsters.json entry has challenge mode stats.
+pub fn is_challenge_mode(monster: &MonsterJson) -> bool {
+    monster
+        .info
+        .version
+        .as_deref()
+        .is_some_and(|version| version.contains("Challenge Mode"))
+}
+
+impl CoxContext {
+    pub fn applies_to(monster: &MonsterJson) -> bool {
+        monster.has_attribute(MonsterAttribute::Xerician)
+    }
+
+    /// Scales the monster's levels for this raid. `player_combat` and
+    /// `player_hitpoints` stand in for any party levels left unset. In
+    /// challenge mode, a normal entry takes the stats and bonuses of its
+    /// challenge mode entry.
+    pub fn apply(&self, monster: &mut MonsterJson, player_combat: u32, player_hitpoints: u32) {
+        if !self.enabled || !Self::applies_to(monster) {
+            return;
+        }
+        let scale = |level: i32, num: u32, den: u32| (level.max(0) as u32 * num / den) as i32;
+
+        if self.challenge_mode && !is_challenge_mode(monster) {
+            if let Some(version) = challenge_mode_counterpart(monster, true) {
+                monster.stats = version.stats;
+                monster.bonuses = version.bonuses;
+            } else {
+                let (num, den) = COX_CM_LEVELS;
+                let stats = &mut monster.stats;
+                let levels = [
+                    &mut stats.hitpoints,
+                    &mut stats.attack,
+                    &mut stats.strength,
+                    &mut stats.defence,
+                    &mut stats.ranged,
+                    &mut stats.magic,
+                ];
+                for level in levels {
+                    *level = scale(*level, num, den);
+                }
+            }
+        }
+        if COX_UNSCALED.contains(&monster.info.name.as_str()) {
+            return;
+        }
+
+        let combat = self.max_combat.unwrap_or(player_combat).clamp(3, 126);
+        let hitpoints = self.max_hitpoints.unwrap_or(player_hitpoints).clamp(10, 99);
+        let party = self.party_size.clamp(1, COX_MAX_PARTY_SIZE);
+        let extra = party - 1;
+        let sqrt_extra = (extra as f64).sqrt().floor() as u32;
+        let stats = &mut monster.stats;
+
+        // Hitpoints and offensive levels scale with the highest combat level
+        stats.hitpoints = scale(stats.hitpoints, combat, 126) * (1 + party as i32 / 2);
+        let offence = sqrt_extra * 7 + extra + 100;
+        let offensive = [
+            &mut stats.attack,
+            &mut stats.strength,
+            &mut stats.ranged,
+            &mut stats.magic,
+        ];
+        for level in offensive {
+            *level = scale(scale(*level, combat, 126), offence, 100);
+        }
+
+        // Defence scales with the highest Hitpoints level
+        let defence = sqrt_extra + extra * 7 / 10 + 100;
+        stats.defence = scale(scale(stats.defence, hitpoints, 99), defence, 100);
+    }
+}
+
+// Version label without its mode, so "Enraged (Challenge Mode)" matches
+// "Enraged" and "Challenge Mode" matches "Normal"
+fn without_mode(monster: &MonsterJson) -> String {
+    monster
+        .info
+        .version
+        .as_deref()
+        .unwrap_or_default()
+        .replace("(Challenge Mode)", "")
+        .replace("Challenge Mode", "")
+        .replace("Normal", "")
+        .trim()
+        .to_string()
+}
+
+/// The monsters.json entry for the same monster in or out of challenge mode.
+pub fn challenge_mode_counterpart(
+    monster: &MonsterJson,
+    challenge_mode: bool,
+) -> Option<MonsterJson> {
+    let label = without_mode(monster);
+    versions_of(&monster.info.name, &[])
+        .into_iter()
+        .find(|version| {
+            is_challenge_mode(version) == challenge_mode && without_mode(version) == label
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn toa(invocations: &[&str]) -> ToaContext {
+        let mut toa = ToaContext {
+            enabled: true,
+            ..ToaContext::default()
+        };
+        for name in invocations {
+            let index = TOA_INVOCATIONS
+                .iter()
+                .position(|i| i.name == *name)
+                .unwrap();
+            toa.set_invocation(index, true);
+        }
+        toa
+    }
+
+    fn monster(name: &str, hitpoints: i32, defence: i32) -> MonsterJson {
+        let mut monster = MonsterJson::blank(-1);
+        monster.info.name = name.to_string();
+        monster.stats.hitpoints = hitpoints;
+        monster.stats.defence = defence;
+        monster
+    }
+
+    #[test]
+    fn invocations_add_up_to_the_raid_level() {
+        // Tiers are listed lowest first, so picking every invocation in order
+        // leaves the top tier of each group and reaches the highest level
+        let mut every = toa(&[]);
+        for index in 0..TOA_INVOCATIONS.len() {
+            every.set_invocation(index, true);
+        }
+        assert_eq!(every.raid_level(), TOA_MAX_RAID_LEVEL);
+
+        // Picking a tier drops the other tiers of its group
+        let toa = toa(&["Try Again", "Hardcore Run", "Insanity"]);
+        assert_eq!(toa.raid_level(), 75);
+    }
+
+    #[test]
+    fn scales_hitpoints_and_defence_with_raid_level() {
+        let toa = toa(&[
+            "Insanity",
+            "Walk the Path",
+            "Pathmaster",
+            "No Help Needed",
+            "Dehydration",
+            "Hardcore Run",
+            "Sprint for It",
+            "Deadly Prayers",
+            "Blood Thinners",
+            "Jungle Japes",
+        ]);
+        assert_eq!(toa.raid_level(), 300);
+
+        // Akkha at raid level 300, solo on the first path
+        let mut akkha = monster("Akkha", 400, 80);
+        toa.apply(&mut akkha);
+        assert_eq!(akkha.stats.hitpoints, 880);
+        assert_eq!(akkha.stats.defence, 176);
+
+        // The path level only adds hitpoints, and only to path monsters
+        let path = ToaContext {
+            path_level: 1,
+            ..toa
+        };
+        let mut scarab = monster("Arcane Scarab", 40, 60);
+        path.apply(&mut scarab);
+        assert_eq!(scarab.stats.hitpoints, 95);
+        assert_eq!(scarab.stats.defence, 132);
+        let mut baboon = monster("Baboon Brawler", 40, 60);
+        path.apply(&mut baboon);
+        assert_eq!(baboon.stats.hitpoints, 90);
+
+        // Monsters outside the raid are left alone
+        let mut goblin = monster("Goblin", 5, 1);
+        toa.apply(&mut goblin);
+        assert_eq!((goblin.stats.hitpoints, goblin.stats.defence), (5, 1));
+    }
+
+    #[test]
+    fn scales_hitpoints_with_party_size() {
+        let toa = ToaContext {
+            party_size: 4,
+            ..toa(&[])
+        };
+        // Second and third players add 90% each, the fourth 60%
+        let akkha = monster("Akkha", 400, 80);
+        assert!((toa.hitpoints_factor(&akkha) - 3.4).abs() < 1e-9);
+    }
+
+    fn cox_entry(name: &str, version: &str) -> MonsterJson {
+        versions_of(name, &[])
+            .into_iter()
+            .find(|monster| monster.info.version.as_deref() == Some(version))
+            .unwrap()
+    }
+
+    fn cox(challenge_mode: bool) -> CoxContext {
+        CoxContext {
+            enabled: true,
+            challenge_mode,
+            ..CoxContext::default()
+        }
+    }
+
+    #[test]
+    fn challenge_mode_uses_the_challenge_mode_entry() {
+        // A solo maxed player leaves the listed levels as they are
+        let mut guardian = cox_entry("Guardian (Chambers of Xeric)", "Normal");
+        cox(true).apply(&mut guardian, 126, 99);
+        let cm = cox_entry("Guardian (Chambers of Xeric)", "Challenge Mode");
+        assert_eq!(guardian.stats, cm.stats);
+        assert_eq!(guardian.stats.hitpoints, 375);
+        assert_eq!(guardian.stats.attack, 210);
+        assert_eq!(guardian.stats.defence, 150);
+    }
+
+    #[test]
+    fn challenge_mode_without_an_entry_raises_every_level() {
+        let mut monster = monster("Unlisted", 100, 100);
+        monster.info.attributes = Some(vec!["xerician".to_string()]);
+        monster.stats.attack = 50;
+        monster.stats.magic = 50;
+        cox(true).apply(&mut monster, 126, 99);
+        let stats = monster.stats;
+        assert_eq!((stats.hitpoints, stats.defence), (150, 150));
+        assert_eq!((stats.attack, stats.magic), (75, 75));
+    }
+
+    #[test]
+    fn scales_with_the_party() {
+        let mut guardian = cox_entry("Guardian (Chambers of Xeric)", "Normal");
+        let party = CoxContext {
+            party_size: 5,
+            ..cox(false)
+        };
+        party.apply(&mut guardian, 126, 99);
+        // Hitpoints triple for 5 players. 4 extra players add 2*7+4 = 18% to
+        // offence and 2+2 = 4% to Defence
+        assert_eq!(guardian.stats.hitpoints, 750);
+        assert_eq!(guardian.stats.attack, 165);
+        assert_eq!(guardian.stats.defence, 104);
+    }
+
+    #[test]
+    fn leaves_the_great_olm_to_its_own_scaling() {
+        let mut head = cox_entry("Great Olm", "Head");
+        let party = CoxContext {
+            party_size: 5,
+            ..cox(true)
+        };
+        party.apply(&mut head, 100, 80);
+        assert_eq!(
+            head.stats,
+            cox_entry("Great Olm", "Head (Challenge Mode)").stats
+        );
+    }
+}
@@ -0,0 +1,76 @@
+//! Combat experience from kills.
+//!
+//! Experience is awarded per point of damage dealt, so a kill is worth the
+//! monster's hitpoints times the rate for the attack style. Magic only counts
+//! the damage part; the base experience of each cast isn't included.
+
+use crate::calc::CombatClass;
+use crate::skill::Skill;
+use osrs::types::equipment::CombatStance;
+use osrs::types::player::Player;
+
+/// Hitpoints experience per point of damage with every style.
+const HITPOINTS_XP: f64 = 4.0 / 3.0;
+
+/// Experience per point of damage in each skill for the active style.
+pub fn xp_per_damage(player: &Player) -> Vec<(Skill, f64)> {
+    let class = CombatClass::of(player.combat_type());
+    let stance = player.combat_stance();
+    let mut rates = match (class, stance) {
+        (CombatClass::Melee, CombatStance::Accurate) => vec![(Skill::Attack, 4.0)],
+        (CombatClass::Melee, CombatStance::Aggressive) => vec![(Skill::Strength, 4.0)],
+        (CombatClass::Melee, CombatStance::Defensive) => vec![(Skill::Defence, 4.0)],
+        (CombatClass::Melee, _) => vec![
+            (Skill::Attack, 4.0 / 3.0),
+            (Skill::Strength, 4.0 / 3.0),
+            (Skill::Defence, 4.0 / 3.0),
+        ],
+        (CombatClass::Ranged, CombatStance::Longrange) => {
+            vec![(Skill::Ranged, 2.0), (Skill::Defence, 2.0)]
+        }
+        (CombatClass::Ranged, _) => vec![(Skill::Ranged, 4.0)],
+        (CombatClass::Magic, CombatStance::Longrange | CombatStance::DefensiveAutocast) => {
+            vec![(Skill::Magic, 4.0 / 3.0), (Skill::Defence, 1.0)]
+        }
+        (CombatClass::Magic, _) => vec![(Skill::Magic, 2.0)],
+    };
+    rates.push((Skill::Hitpoints, HITPOINTS_XP));
+    rates
+}
+
+/// Experience per hour in each skill from killing a monster with
+/// `monster_hitpoints` at `kills_per_hour`.
+pub fn xp_per_hour(
+    player: &Player,
+    monster_hitpoints: u32,
+    kills_per_hour: f64,
+) -> Vec<(Skill, f64)> {
+    let damage_per_hour = monster_hitpoints as f64 * kills_per_hour;
+    xp_per_damage(player)
+        .into_iter()
+        .map(|(skill, rate)| (skill, rate * damage_per_hour))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_style_gives_hitpoints_experience() {
+        let rates = xp_per_damage(&Player::default());
+        assert_eq!(rates.last(), Some(&(Skill::Hitpoints, HITPOINTS_XP)));
+        // Unarmed is melee, which gives 4 combat experience per damage on any
+        // stance
+        let combat: f64 = rates[..rates.len() - 1].iter().map(|(_, rate)| rate).sum();
+        assert!((combat - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn experience_per_hour() {
+        // 30 kills an hour of a 100 hitpoint monster is 3000 damage
+        let rates = xp_per_hour(&Player::default(), 100, 30.0);
+        let hitpoints = rates.iter().find(|(skill, _)| *skill == Skill::Hitpoints);
+        assert!(hitpoints.is_some_and(|(_, xp)| (xp - 4000.0).abs() < 1e-9));
+    }
+}
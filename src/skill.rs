@@ -0,0 +1,366 @@
+use crate::{BONUSES_ASSETS, SKILLS_ASSETS};
+use osrs::types::player::Player;
+
+// Define skill types in hiscore order
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Skill {
+    Attack,
+    Defence,
+    Strength,
+    Hitpoints,
+    Ranged,
+    Prayer,
+    Magic,
+    Cooking,
+    Woodcutting,
+    Fletching,
+    Fishing,
+    Firemaking,
+    Crafting,
+    Smithing,
+    Mining,
+    Herblore,
+    Agility,
+    Thieving,
+    Slayer,
+    Farming,
+    Runecraft,
+    Hunter,
+    Construction,
+}
+
+impl Skill {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Skill::Attack => "Attack",
+            Skill::Defence => "Defence",
+            Skill::Strength => "Strength",
+            Skill::Hitpoints => "Hitpoints",
+            Skill::Ranged => "Ranged",
+            Skill::Prayer => "Prayer",
+            Skill::Magic => "Magic",
+            Skill::Cooking => "Cooking",
+            Skill::Woodcutting => "Woodcutting",
+            Skill::Fletching => "Fletching",
+            Skill::Fishing => "Fishing",
+            Skill::Firemaking => "Firemaking",
+            Skill::Crafting => "Crafting",
+            Skill::Smithing => "Smithing",
+            Skill::Mining => "Mining",
+            Skill::Herblore => "Herblore",
+            Skill::Agility => "Agility",
+            Skill::Thieving => "Thieving",
+            Skill::Slayer => "Slayer",
+            Skill::Farming => "Farming",
+            Skill::Runecraft => "Runecraft",
+            Skill::Hunter => "Hunter",
+            Skill::Construction => "Construction",
+        }
+    }
+
+    /// Whether the level is stored on `Player.stats` and used by the calculator.
+    pub fn is_player_stat(&self) -> bool {
+        PLAYER_SKILLS.contains(self)
+    }
+
+    /// Lowest level the skill can have on a fresh account.
+    pub fn min_level(&self) -> u32 {
+        if *self == Skill::Hitpoints {
+            10
+        } else {
+            1
+        }
+    }
+
+    pub fn icon_path(&self) -> String {
+        if self.is_player_stat() {
+            format!("{BONUSES_ASSETS}/{}.png", self.name().to_lowercase())
+        } else {
+            format!("{SKILLS_ASSETS}/{}.png", self.name().to_lowercase())
+        }
+    }
+}
+
+pub const ALL_SKILLS: [Skill; 23] = [
+    Skill::Attack,
+    Skill::Defence,
+    Skill::Strength,
+    Skill::Hitpoints,
+    Skill::Ranged,
+    Skill::Prayer,
+    Skill::Magic,
+    Skill::Cooking,
+    Skill::Woodcutting,
+    Skill::Fletching,
+    Skill::Fishing,
+    Skill::Firemaking,
+    Skill::Crafting,
+    Skill::Smithing,
+    Skill::Mining,
+    Skill::Herblore,
+    Skill::Agility,
+    Skill::Thieving,
+    Skill::Slayer,
+    Skill::Farming,
+    Skill::Runecraft,
+    Skill::Hunter,
+    Skill::Construction,
+];
+
+/// Skills in the order of the in-game skills tab, read row by row.
+pub const SKILLS_TAB_LAYOUT: [Skill; 23] = [
+    Skill::Attack,
+    Skill::Hitpoints,
+    Skill::Mining,
+    Skill::Strength,
+    Skill::Agility,
+    Skill::Smithing,
+    Skill::Defence,
+    Skill::Herblore,
+    Skill::Fishing,
+    Skill::Ranged,
+    Skill::Thieving,
+    Skill::Cooking,
+    Skill::Prayer,
+    Skill::Crafting,
+    Skill::Firemaking,
+    Skill::Magic,
+    Skill::Fletching,
+    Skill::Woodcutting,
+    Skill::Runecraft,
+    Skill::Slayer,
+    Skill::Farming,
+    Skill::Construction,
+    Skill::Hunter,
+];
+
+/// Skills stored on `Player.stats`.
+pub const PLAYER_SKILLS: [Skill; 9] = [
+    Skill::Attack,
+    Skill::Strength,
+    Skill::Defence,
+    Skill::Ranged,
+    Skill::Magic,
+    Skill::Hitpoints,
+    Skill::Prayer,
+    Skill::Mining,
+    Skill::Herblore,
+];
+
+pub const COMBAT_SKILLS: [Skill; 6] = [
+    Skill::Attack,
+    Skill::Strength,
+    Skill::Defence,
+    Skill::Ranged,
+    Skill::Magic,
+    Skill::Hitpoints,
+];
+
+/// Combat level (with the fractional part) from base levels.
+pub fn combat_level(level: impl Fn(Skill) -> u32) -> f64 {
+    let base =
+        0.25 * (level(Skill::Defence) + level(Skill::Hitpoints) + level(Skill::Prayer) / 2) as f64;
+    let melee = 0.325 * (level(Skill::Attack) + level(Skill::Strength)) as f64;
+    let ranged = 0.325 * (level(Skill::Ranged) * 3 / 2) as f64;
+    let magic = 0.325 * (level(Skill::Magic) * 3 / 2) as f64;
+    base + melee.max(ranged).max(magic)
+}
+
+/// The skills needing the fewest levels to raise combat level, with that
+/// number of levels. Returns `None` at max combat.
+pub fn next_combat_level(level: impl Fn(Skill) -> u32) -> Option<(Vec<Skill>, u32)> {
+    let current = combat_level(&level).floor();
+    let candidates = [
+        Skill::Attack,
+        Skill::Strength,
+        Skill::Defence,
+        Skill::Hitpoints,
+        Skill::Prayer,
+        Skill::Ranged,
+        Skill::Magic,
+    ];
+
+    let mut best: Option<(Vec<Skill>, u32)> = None;
+    for skill in candidates {
+        let start = level(skill);
+        let needed = (start + 1..=99).find_map(|target| {
+            let raised = combat_level(|s| if s == skill { target } else { level(s) });
+            (raised.floor() > current).then_some(target - start)
+        });
+        let Some(needed) = needed else {
+            continue;
+        };
+        match &mut best {
+            Some((skills, fewest)) if needed == *fewest => skills.push(skill),
+            Some((_, fewest)) if needed > *fewest => {}
+            _ => best = Some((vec![skill], needed)),
+        }
+    }
+
+    best
+}
+
+// Helper functions to get and set levels of the skills stored on the player
+pub fn get_skill_levels(player: &Player, skill: Skill) -> Option<(u32, u32)> {
+    let stat = match skill {
+        Skill::Attack => &player.stats.attack,
+        Skill::Strength => &player.stats.strength,
+        Skill::Defence => &player.stats.defence,
+        Skill::Ranged => &player.stats.ranged,
+        Skill::Magic => &player.stats.magic,
+        Skill::Hitpoints => &player.stats.hitpoints,
+        Skill::Prayer => &player.stats.prayer,
+        Skill::Mining => &player.stats.mining,
+        Skill::Herblore => &player.stats.herblore,
+        _ => return None,
+    };
+    Some((stat.base, stat.current))
+}
+
+pub fn set_skill_base_level(player: &mut Player, skill: Skill, level: u32) {
+    match skill {
+        Skill::Attack => player.stats.attack.base = level,
+        Skill::Strength => player.stats.strength.base = level,
+        Skill::Defence => player.stats.defence.base = level,
+        Skill::Ranged => player.stats.ranged.base = level,
+        Skill::Magic => player.stats.magic.base = level,
+        Skill::Hitpoints => player.stats.hitpoints.base = level,
+        Skill::Prayer => player.stats.prayer.base = level,
+        Skill::Mining => player.stats.mining.base = level,
+        Skill::Herblore => player.stats.herblore.base = level,
+        _ => {}
+    }
+}
+
+pub fn set_skill_current_level(player: &mut Player, skill: Skill, level: u32) {
+    match skill {
+        Skill::Attack => player.stats.attack.current = level,
+        Skill::Strength => player.stats.strength.current = level,
+        Skill::Defence => player.stats.defence.current = level,
+        Skill::Ranged => player.stats.ranged.current = level,
+        Skill::Magic => player.stats.magic.current = level,
+        Skill::Hitpoints => player.stats.hitpoints.current = level,
+        Skill::Prayer => player.stats.prayer.current = level,
+        Skill::Mining => player.stats.mining.current = level,
+        Skill::Herblore => player.stats.herblore.current = level,
+        _ => {}
+    }
+}
+
+/// Total experience needed to reach a level.
+pub fn xp_for_level(level: u32) -> u64 {
+    let points: f64 = (1..level.max(1))
+        .map(|l| (l as f64 + 300.0 * 2f64.powf(l as f64 / 7.0)).floor())
+        .sum();
+    (points / 4.0).floor() as u64
+}
+
+/// Level reached with the given experience, capped at 99.
+pub fn level_for_xp(xp: u64) -> u32 {
+    (1..99)
+        .find(|&level| xp_for_level(level + 1) > xp)
+        .unwrap_or(99)
+}
+
+/// A value typed into a skill field: a plain level, or experience when it is
+/// larger than 99 or written with a `k`/`m` suffix.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LevelInput {
+    Level(u32),
+    Xp(u64),
+}
+
+pub fn parse_level_input(input: &str) -> Option<LevelInput> {
+    let cleaned: String = input
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ',')
+        .collect::<String>()
+        .to_lowercase();
+    let cleaned = cleaned.strip_suffix("xp").unwrap_or(&cleaned);
+
+    let (number, multiplier) = if let Some(number) = cleaned.strip_suffix('k') {
+        (number, Some(1_000.0))
+    } else if let Some(number) = cleaned.strip_suffix('m') {
+        (number, Some(1_000_000.0))
+    } else {
+        (cleaned, None)
+    };
+
+    match multiplier {
+        Some(multiplier) => {
+            let value = number.parse::<f64>().ok()?;
+            (value >= 0.0).then(|| LevelInput::Xp((value * multiplier).round() as u64))
+        }
+        None => {
+            let value = number.parse::<u64>().ok()?;
+            if value <= 99 {
+                Some(LevelInput::Level(value as u32))
+            } else {
+                Some(LevelInput::Xp(value))
+            }
+        }
+    }
+}
+
+/// Formats experience with thousands separators.
+pub fn format_xp(xp: u64) -> String {
+    let digits = xp.to_string();
+    let mut formatted = String::new();
+    for (idx, digit) in digits.chars().enumerate() {
+        if idx > 0 && (digits.len() - idx) % 3 == 0 {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn experience_table() {
+        assert_eq!(xp_for_level(1), 0);
+        assert_eq!(xp_for_level(2), 83);
+        assert_eq!(xp_for_level(50), 101_333);
+        assert_eq!(xp_for_level(92), 6_517_253);
+        assert_eq!(xp_for_level(99), 13_034_431);
+
+        assert_eq!(level_for_xp(0), 1);
+        assert_eq!(level_for_xp(82), 1);
+        assert_eq!(level_for_xp(83), 2);
+        assert_eq!(level_for_xp(13_034_430), 98);
+        assert_eq!(level_for_xp(200_000_000), 99);
+    }
+
+    #[test]
+    fn combat_levels() {
+        let fresh = |skill: Skill| skill.min_level();
+        assert_eq!(combat_level(fresh).floor(), 3.0);
+        assert!((combat_level(|_| 99) - 126.1).abs() < 1e-9);
+        assert_eq!(next_combat_level(|_| 99), None);
+
+        // Two levels in any attacking skill beat three in Hitpoints or Defence
+        let (skills, levels) = next_combat_level(fresh).unwrap();
+        assert_eq!(levels, 2);
+        assert_eq!(
+            skills,
+            [Skill::Attack, Skill::Strength, Skill::Ranged, Skill::Magic]
+        );
+    }
+
+    #[test]
+    fn level_inputs() {
+        assert_eq!(parse_level_input("99"), Some(LevelInput::Level(99)));
+        assert_eq!(parse_level_input("100"), Some(LevelInput::Xp(100)));
+        assert_eq!(
+            parse_level_input("13,034,431"),
+            Some(LevelInput::Xp(13_034_431))
+        );
+        assert_eq!(parse_level_input("1.5m"), Some(LevelInput::Xp(1_500_000)));
+        assert_eq!(parse_level_input("250k xp"), Some(LevelInput::Xp(250_000)));
+        assert_eq!(parse_level_input("abc"), None);
+        assert_eq!(format_xp(13_034_431), "13,034,431");
+    }
+}
@@ -0,0 +1,212 @@
+/// Elements of combat spells and monster weaknesses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Element {
+    Air,
+    Water,
+    Earth,
+    Fire,
+}
+
+impl Element {
+    /// Parses an element as written in monsters.json, e.g. "fire".
+    pub fn from_json(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "air" => Some(Element::Air),
+            "water" => Some(Element::Water),
+            "earth" => Some(Element::Earth),
+            "fire" => Some(Element::Fire),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Element::Air => "Air",
+            Element::Water => "Water",
+            Element::Earth => "Earth",
+            Element::Fire => "Fire",
+        }
+    }
+}
+
+/// Spellbooks with combat spells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Spellbook {
+    #[default]
+    Standard,
+    Ancient,
+}
+
+impl Spellbook {
+    pub const ALL: [Spellbook; 2] = [Spellbook::Standard, Spellbook::Ancient];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Spellbook::Standard => "Standard",
+            Spellbook::Ancient => "Ancient Magicks",
+        }
+    }
+}
+
+/// A combat spell cast with an unpowered staff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Spell {
+    pub name: &'static str,
+    /// Magic level needed to cast it.
+    pub level: u32,
+    pub base_max_hit: u32,
+    pub element: Option<Element>,
+    pub spellbook: Spellbook,
+    /// Weapons the spell can only be cast with, including their variants, or
+    /// empty when any weapon will do.
+    pub staves: &'static [&'static str],
+}
+
+const fn spell(
+    name: &'static str,
+    level: u32,
+    base_max_hit: u32,
+    element: Option<Element>,
+) -> Spell {
+    Spell {
+        name,
+        level,
+        base_max_hit,
+        element,
+        spellbook: Spellbook::Standard,
+        staves: &[],
+    }
+}
+
+const fn ancient(name: &'static str, level: u32, base_max_hit: u32) -> Spell {
+    Spell {
+        spellbook: Spellbook::Ancient,
+        ..spell(name, level, base_max_hit, None)
+    }
+}
+
+impl Spell {
+    const fn requires(self, staves: &'static [&'static str]) -> Spell {
+        Spell { staves, ..self }
+    }
+
+    /// Whether the spell can be cast while wielding `weapon`.
+    pub fn castable_with(&self, weapon: &str) -> bool {
+        self.staves.is_empty() || self.staves.iter().any(|staff| weapon.starts_with(staff))
+    }
+}
+
+const AIR: Option<Element> = Some(Element::Air);
+const WATER: Option<Element> = Some(Element::Water);
+const EARTH: Option<Element> = Some(Element::Earth);
+const FIRE: Option<Element> = Some(Element::Fire);
+
+const IBAN_STAVES: [&str; 1] = ["Iban's staff"];
+const SARADOMIN_STAVES: [&str; 2] = ["Saradomin staff", "Staff of light"];
+const GUTHIX_STAVES: [&str; 3] = ["Guthix staff", "Void knight mace", "Staff of balance"];
+const ZAMORAK_STAVES: [&str; 3] = [
+    "Zamorak staff",
+    "Staff of the dead",
+    "Toxic staff of the dead",
+];
+
+/// Combat spells from the standard and Ancient spellbooks.
+pub const SPELLS: [Spell; 40] = [
+    spell("Wind Strike", 1, 2, AIR),
+    spell("Water Strike", 5, 4, WATER),
+    spell("Earth Strike", 9, 6, EARTH),
+    spell("Fire Strike", 13, 8, FIRE),
+    spell("Wind Bolt", 17, 9, AIR),
+    spell("Water Bolt", 23, 10, WATER),
+    spell("Earth Bolt", 29, 11, EARTH),
+    spell("Fire Bolt", 35, 12, FIRE),
+    spell("Wind Blast", 41, 13, AIR),
+    spell("Water Blast", 47, 14, WATER),
+    spell("Earth Blast", 53, 15, EARTH),
+    spell("Fire Blast", 59, 16, FIRE),
+    spell("Wind Wave", 62, 17, AIR),
+    spell("Water Wave", 65, 18, WATER),
+    spell("Earth Wave", 70, 19, EARTH),
+    spell("Fire Wave", 75, 20, FIRE),
+    spell("Wind Surge", 81, 21, AIR),
+    spell("Water Surge", 85, 22, WATER),
+    spell("Earth Surge", 90, 23, EARTH),
+    spell("Fire Surge", 95, 24, FIRE),
+    spell("Iban Blast", 50, 25, None).requires(&IBAN_STAVES),
+    spell("Saradomin Strike", 60, 20, None).requires(&SARADOMIN_STAVES),
+    spell("Claws of Guthix", 60, 20, None).requires(&GUTHIX_STAVES),
+    spell("Flames of Zamorak", 60, 20, None).requires(&ZAMORAK_STAVES),
+    ancient("Smoke Rush", 50, 13),
+    ancient("Shadow Rush", 52, 14),
+    ancient("Blood Rush", 56, 15),
+    ancient("Ice Rush", 58, 16),
+    ancient("Smoke Burst", 62, 17),
+    ancient("Shadow Burst", 64, 18),
+    ancient("Blood Burst", 68, 21),
+    ancient("Ice Burst", 70, 22),
+    ancient("Smoke Blitz", 74, 23),
+    ancient("Shadow Blitz", 76, 24),
+    ancient("Blood Blitz", 80, 25),
+    ancient("Ice Blitz", 82, 26),
+    ancient("Smoke Barrage", 86, 27),
+    ancient("Shadow Barrage", 88, 28),
+    ancient("Blood Barrage", 92, 29),
+    ancient("Ice Barrage", 94, 30),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn max_hit(name: &str) -> u32 {
+        SPELLS
+            .iter()
+            .find(|spell| spell.name == name)
+            .unwrap()
+            .base_max_hit
+    }
+
+    #[test]
+    fn ancient_max_hits() {
+        let tiers = ["Rush", "Burst", "Blitz", "Barrage"];
+        let elements = ["Smoke", "Shadow", "Blood", "Ice"];
+        let max_hits: Vec<u32> = tiers
+            .iter()
+            .flat_map(|tier| elements.map(|element| max_hit(&format!("{element} {tier}"))))
+            .collect();
+        assert_eq!(
+            max_hits,
+            [13, 14, 15, 16, 17, 18, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30]
+        );
+    }
+
+    #[test]
+    fn staff_requirements() {
+        let iban = SPELLS
+            .iter()
+            .find(|spell| spell.name == "Iban Blast")
+            .unwrap();
+        assert!(iban.castable_with("Iban's staff (u)"));
+        assert!(!iban.castable_with("Staff of fire"));
+        let flames = SPELLS
+            .iter()
+            .find(|spell| spell.name == "Flames of Zamorak")
+            .unwrap();
+        assert!(flames.castable_with("Toxic staff of the dead"));
+        let barrage = SPELLS
+            .iter()
+            .find(|spell| spell.name == "Ice Barrage")
+            .unwrap();
+        assert_eq!(barrage.spellbook, Spellbook::Ancient);
+        assert!(barrage.castable_with("Kodai wand"));
+    }
+
+    #[test]
+    fn standard_max_hits() {
+        assert_eq!(max_hit("Wind Strike"), 2);
+        assert_eq!(max_hit("Fire Bolt"), 12);
+        assert_eq!(max_hit("Fire Wave"), 20);
+        assert_eq!(max_hit("Fire Surge"), 24);
+        assert_eq!(max_hit("Iban Blast"), 25);
+    }
+}
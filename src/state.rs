@@ -1,6 +1,483 @@
-use osrs::types::player::Player;
+use crate::boosts::{potion_effects, BoostDecay, CustomBoost, DecaySpan, StatModifier};
+use crate::calc::{fight_stats_at, FightStats, OffensiveLevels};
+use crate::defence::{average_damage_per_tick, monster_attacks, Antifire, Protection};
+use crate::hiscores::{
+    parse_activity_entries, parse_skill_entries, ActivityEntry, GameMode, HiscoreError,
+};
+use crate::monster::{save_custom_monsters, MonsterJson, SelectedMonster};
+use crate::profiles::ProfileStore;
+use crate::raids::{CoxContext, ToaContext};
+use crate::skill::{
+    combat_level, get_skill_levels, level_for_xp, set_skill_base_level, set_skill_current_level,
+    xp_for_level, Skill, PLAYER_SKILLS,
+};
+use crate::spells::{Spell, Spellbook};
+use crate::supplies::{estimate_trip, TripEstimate, TripSetup};
+use crate::unlocks::{inferred_unlocks, Unlock};
+use osrs::types::player::{parse_player_data, Player};
+use osrs::types::potions::Potion;
+use std::collections::HashMap;
+use strum::IntoEnumIterator;
+
+/// Passes used to settle the fight length that boosts are averaged over.
+const DECAY_PASSES: usize = 3;
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct AppState {
     pub player: Player,
+    pub boost_decay: BoostDecay,
+    pub custom_boosts: Vec<CustomBoost>,
+    /// Manually entered current levels, e.g. Hitpoints after taking damage.
+    pub current_overrides: HashMap<Skill, u32>,
+    /// Levels of the skills that `Player.stats` doesn't track.
+    pub other_levels: HashMap<Skill, u32>,
+    /// Exact experience, where known from the hiscores or typed in.
+    pub skill_xp: HashMap<Skill, u64>,
+    /// Spell cast when using magic without a powered staff.
+    pub spell: Option<Spell>,
+    /// Spellbook the player is on, limiting the spells offered.
+    pub spellbook: Spellbook,
+    /// The monster being fought.
+    pub monster: Option<SelectedMonster>,
+    /// Tombs of Amascut settings that scale the selected monster.
+    pub toa: ToaContext,
+    /// Chambers of Xeric settings that scale the selected monster.
+    pub cox: CoxContext,
+    /// Overhead prayer used against the monster.
+    pub protection: Option<Protection>,
+    /// Antifire potion used against dragonfire.
+    pub antifire: Antifire,
+    /// Inventory brought on each trip.
+    pub trip: TripSetup,
+    /// User-made monsters, listed alongside monsters.json.
+    pub custom_monsters: Vec<MonsterJson>,
+    /// Ranked clue, activity and boss rows from the hiscores.
+    pub activities: Vec<ActivityEntry>,
+    /// Unlocks switched on or off, pre-filled from hiscore kill counts.
+    /// Anything not listed is assumed to be unlocked.
+    pub unlock_overrides: HashMap<Unlock, bool>,
+    /// Unlocks switched on or off by hand for the active profile. These win
+    /// over kill counts and are kept when new stats are looked up.
+    pub manual_unlocks: HashMap<Unlock, bool>,
+    /// CORS proxy prefix for looking up hiscores from the browser when the
+    /// app is hosted without its server.
+    pub hiscore_proxy: Option<String>,
+    /// Saved accounts and their stat snapshots.
+    pub profiles: ProfileStore,
+}
+
+impl AppState {
+    /// Base and current level of any skill.
+    pub fn skill_levels(&self, skill: Skill) -> (u32, u32) {
+        get_skill_levels(&self.player, skill).unwrap_or_else(|| {
+            let level = self
+                .other_levels
+                .get(&skill)
+                .copied()
+                .unwrap_or(skill.min_level());
+            (level, level)
+        })
+    }
+
+    pub fn base_level(&self, skill: Skill) -> u32 {
+        self.skill_levels(skill).0
+    }
+
+    pub fn combat_level(&self) -> u32 {
+        combat_level(|skill| self.base_level(skill)).floor() as u32
+    }
+
+    /// Experience in a skill, assuming the start of the level when unknown.
+    pub fn xp(&self, skill: Skill) -> u64 {
+        let base = self.base_level(skill);
+        match self.skill_xp.get(&skill) {
+            Some(&xp) if level_for_xp(xp) == base => xp,
+            _ => xp_for_level(base),
+        }
+    }
+
+    pub fn set_xp(&mut self, skill: Skill, xp: u64) {
+        self.set_base_level(skill, level_for_xp(xp).max(skill.min_level()));
+        self.skill_xp.insert(skill, xp);
+    }
+
+    pub fn set_base_level(&mut self, skill: Skill, level: u32) {
+        self.skill_xp.remove(&skill);
+        if skill.is_player_stat() {
+            set_skill_base_level(&mut self.player, skill, level);
+            self.refresh_current_stats();
+        } else {
+            self.other_levels.insert(skill, level);
+        }
+    }
+
+    /// Replaces every level and kill count with those in `index_lite` data,
+    /// clearing manual level overrides.
+    pub fn apply_hiscore_data(
+        &mut self,
+        data: String,
+        name: Option<String>,
+    ) -> Result<(), HiscoreError> {
+        let skill_entries = parse_skill_entries(&data)?;
+        let activities = parse_activity_entries(&data);
+        self.player.stats = parse_player_data(data).map_err(|e| HiscoreError::Parse {
+            line: None,
+            content: e.to_string(),
+        })?;
+        self.other_levels = skill_entries
+            .iter()
+            .filter(|entry| !entry.skill.is_player_stat())
+            .map(|entry| (entry.skill, entry.level))
+            .collect();
+        self.skill_xp = skill_entries
+            .iter()
+            .filter_map(|entry| entry.xp.map(|xp| (entry.skill, xp)))
+            .collect();
+        self.activities = activities;
+        self.seed_unlocks();
+        if name.is_some() {
+            self.player.attrs.name = name;
+        }
+        self.current_overrides.clear();
+        self.refresh_current_stats();
+        Ok(())
+    }
+
+    /// Loads one of a saved profile's snapshots and makes that profile active.
+    pub fn load_snapshot(&mut self, profile: usize, snapshot: usize) -> Result<(), HiscoreError> {
+        let Some((name, data)) = self.profiles.profiles.get(profile).and_then(|p| {
+            let snapshot = p.snapshots.get(snapshot)?;
+            Some((p.name.clone(), snapshot.data.clone()))
+        }) else {
+            return Ok(());
+        };
+        self.apply_hiscore_data(data, Some(name))?;
+        self.profiles.active = Some(profile);
+        self.profiles.loaded_snapshot = Some(snapshot);
+        self.profiles.save();
+        self.restore_unlocks();
+        Ok(())
+    }
+
+    /// Records stats for an account and makes it the active profile, along
+    /// with its saved unlocks.
+    pub fn record_profile(
+        &mut self,
+        name: &str,
+        mode: Option<GameMode>,
+        data: &str,
+        taken_at: u64,
+    ) {
+        self.profiles.record(name, mode, data, taken_at);
+        self.restore_unlocks();
+    }
+
+    fn restore_unlocks(&mut self) {
+        if let Some(profile) = self.profiles.active_profile() {
+            self.manual_unlocks = profile.unlocks.clone();
+            self.seed_unlocks();
+        }
+    }
+
+    /// Fills in unlocks from kill counts, then lays the manual toggles on top.
+    /// Without any hiscore rows everything counts as unlocked.
+    fn seed_unlocks(&mut self) {
+        self.unlock_overrides.clear();
+        if !self.activities.is_empty() {
+            let inferred = inferred_unlocks(&self.activities);
+            self.unlock_overrides = Unlock::iter()
+                .map(|unlock| (unlock, inferred.contains(&unlock)))
+                .collect();
+        }
+        self.unlock_overrides.extend(&self.manual_unlocks);
+    }
+
+    /// Whether the account has an unlock. Unlocks count as unlocked unless
+    /// kill counts or a manual toggle say otherwise.
+    pub fn is_unlocked(&self, unlock: Unlock) -> bool {
+        self.unlock_overrides.get(&unlock).copied().unwrap_or(true)
+    }
+
+    /// Switches an unlock on or off, saving it with the active profile.
+    pub fn set_unlocked(&mut self, unlock: Unlock, unlocked: bool) {
+        self.unlock_overrides.insert(unlock, unlocked);
+        self.manual_unlocks.insert(unlock, unlocked);
+        if let Some(profile) = self.profiles.active_profile_mut() {
+            profile.unlocks = self.manual_unlocks.clone();
+            self.profiles.save();
+        }
+    }
+
+    /// An unused id for a custom monster. Custom ids are negative so they
+    /// never clash with monsters.json.
+    pub fn next_custom_monster_id(&self) -> i64 {
+        self.custom_monsters
+            .iter()
+            .map(|monster| monster.info.id)
+            .min()
+            .unwrap_or(0)
+            .min(0)
+            - 1
+    }
+
+    /// Saves a custom monster, replacing any with the same name and version.
+    pub fn add_custom_monster(&mut self, monster: MonsterJson) {
+        match self
+            .custom_monsters
+            .iter_mut()
+            .find(|existing| existing.same_entry(&monster))
+        {
+            Some(existing) => *existing = monster,
+            None => self.custom_monsters.push(monster),
+        }
+        save_custom_monsters(&self.custom_monsters);
+    }
+
+    pub fn remove_custom_monster(&mut self, idx: usize) {
+        if idx < self.custom_monsters.len() {
+            self.custom_monsters.remove(idx);
+            save_custom_monsters(&self.custom_monsters);
+        }
+    }
+
+    /// A monster with any raid scaling applied on top of the user's edits,
+    /// keeping the share of hitpoints it has left.
+    pub fn scaled(&self, mut monster: SelectedMonster) -> SelectedMonster {
+        let max_hp = monster.max_hp();
+        self.toa.apply(&mut monster.edited);
+        self.cox.apply(
+            &mut monster.edited,
+            self.combat_level(),
+            self.base_level(Skill::Hitpoints),
+        );
+        if max_hp > 0 && monster.max_hp() != max_hp {
+            monster.current_hp =
+                (monster.current_hp as u64 * monster.max_hp() as u64 / max_hp as u64) as u32;
+        }
+        monster
+    }
+
+    /// The selected monster as fought, with raid scaling applied.
+    pub fn target(&self) -> Option<SelectedMonster> {
+        self.monster.clone().map(|monster| self.scaled(monster))
+    }
+
+    /// Supplies, trip length and kills per hour against the selected monster,
+    /// or `None` when it can't be killed.
+    pub fn trip_estimate(&self) -> Option<TripEstimate> {
+        let monster = self.target()?;
+        let stats = self.fight_stats(&monster);
+        self.estimate_for(&monster, &stats)
+    }
+
+    fn estimate_for(&self, monster: &SelectedMonster, stats: &FightStats) -> Option<TripEstimate> {
+        let ttk = stats.ttk_secs?;
+        let attacks = monster_attacks(
+            &self.player,
+            &monster.edited,
+            self.protection,
+            self.antifire,
+        );
+        let redose_secs = self
+            .boost_decay
+            .redose_minutes
+            .map(|minutes| minutes as f64 * 60.0);
+        Some(estimate_trip(
+            &self.player,
+            self.protection,
+            average_damage_per_tick(&attacks),
+            ttk,
+            self.active_potions().len(),
+            redose_secs,
+            &self.trip,
+        ))
+    }
+
+    /// Offensive levels averaged over `secs` of boost decay.
+    pub fn decayed_levels(&self, secs: f64) -> OffensiveLevels {
+        let average = |skill: Skill| {
+            let (base, current) = self.skill_levels(skill);
+            self.boost_decay.average_level(base, current, secs).round() as u32
+        };
+        OffensiveLevels {
+            attack: average(Skill::Attack),
+            strength: average(Skill::Strength),
+            ranged: average(Skill::Ranged),
+            magic: average(Skill::Magic),
+        }
+    }
+
+    /// The player's output against `monster`, with boosts averaged over the
+    /// kill or trip when boost decay is enabled. Also returns the seconds the
+    /// boosts were averaged over.
+    pub fn fight_stats_with_decay(&self, monster: &SelectedMonster) -> (FightStats, Option<f64>) {
+        let full = OffensiveLevels::current(&self.player);
+        let mut stats = fight_stats_at(&self.player, &full, monster, self.spell);
+        if !self.boost_decay.enabled {
+            return (stats, None);
+        }
+
+        // The fight gets longer as the boosts wear off, so settle the length
+        // over a few passes
+        let mut span_secs = None;
+        for _ in 0..DECAY_PASSES {
+            let secs = match self.boost_decay.span {
+                DecaySpan::Kill => stats.ttk_secs,
+                DecaySpan::Trip => self
+                    .estimate_for(monster, &stats)
+                    .and_then(|estimate| estimate.trip_secs)
+                    .or(stats.ttk_secs),
+            };
+            let Some(secs) = secs.filter(|&secs| secs > 0.0) else {
+                break;
+            };
+            span_secs = Some(secs);
+            let levels = self.decayed_levels(secs);
+            stats = fight_stats_at(&self.player, &levels, monster, self.spell);
+        }
+        (stats, span_secs)
+    }
+
+    /// The player's output against `monster`, with boosts averaged over the
+    /// kill or trip when boost decay is enabled.
+    pub fn fight_stats(&self, monster: &SelectedMonster) -> FightStats {
+        self.fight_stats_with_decay(monster).0
+    }
+
+    pub fn active_potions(&self) -> Vec<Potion> {
+        let mut active_potions = Vec::new();
+        let potions = &self.player.potions;
+
+        for boosts in [
+            &potions.attack,
+            &potions.strength,
+            &potions.defence,
+            &potions.ranged,
+            &potions.magic,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            for boost in boosts {
+                if !active_potions.contains(&boost.potion_type) {
+                    active_potions.push(boost.potion_type);
+                }
+            }
+        }
+
+        active_potions
+    }
+
+    /// Every potion and custom boost that changes the given stat.
+    ///
+    /// Boosts to the same stat don't stack, so only the strongest potion boost
+    /// is marked as applied; drains and custom boosts always apply.
+    pub fn stat_modifiers(&self, skill: Skill) -> Vec<StatModifier> {
+        let mut modifiers: Vec<StatModifier> = self
+            .active_potions()
+            .into_iter()
+            .flat_map(|potion| {
+                potion_effects(potion, &self.player)
+                    .into_iter()
+                    .filter(|(affected, amount)| *affected == skill && *amount != 0)
+                    .map(move |(_, amount)| StatModifier {
+                        source: potion.to_string(),
+                        amount,
+                        applied: true,
+                    })
+            })
+            .collect();
+
+        let strongest = modifiers
+            .iter()
+            .enumerate()
+            .filter(|(_, modifier)| modifier.amount > 0)
+            .max_by_key(|(_, modifier)| modifier.amount)
+            .map(|(idx, _)| idx);
+        for (idx, modifier) in modifiers.iter_mut().enumerate() {
+            if modifier.amount > 0 && Some(idx) != strongest {
+                modifier.applied = false;
+            }
+        }
+
+        let base = self.base_level(skill);
+        modifiers.extend(
+            self.custom_boosts
+                .iter()
+                .filter(|boost| boost.skill == skill)
+                .map(|boost| StatModifier {
+                    source: format!("Custom boost ({})", boost.label()),
+                    amount: boost.amount(base),
+                    applied: true,
+                }),
+        );
+
+        modifiers
+    }
+
+    /// Current level of a stat after every applied modifier.
+    pub fn predicted_level(&self, skill: Skill) -> u32 {
+        let base = self.base_level(skill);
+        let net: i32 = self
+            .stat_modifiers(skill)
+            .iter()
+            .filter(|modifier| modifier.applied)
+            .map(|modifier| modifier.amount)
+            .sum();
+        let minimum = if skill == Skill::Hitpoints { 1 } else { 0 };
+        (base as i32 + net).max(minimum) as u32
+    }
+
+    /// Sets a stat's current level by hand, or clears the override when it
+    /// matches the predicted level.
+    pub fn override_current_level(&mut self, skill: Skill, level: u32) {
+        if level == self.predicted_level(skill) {
+            self.current_overrides.remove(&skill);
+        } else {
+            self.current_overrides.insert(skill, level);
+        }
+        self.refresh_current_stats();
+    }
+
+    /// Recalculates current levels from base levels, potions and custom boosts,
+    /// keeping any manual overrides.
+    pub fn refresh_current_stats(&mut self) {
+        self.player.reset_current_stats(true);
+        for skill in PLAYER_SKILLS {
+            let level = match self.current_overrides.get(&skill) {
+                Some(&level) => level,
+                None => self.predicted_level(skill),
+            };
+            set_skill_current_level(&mut self.player, skill, level);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hiscores::ActivityKind;
+
+    fn boss(name: &'static str, score: u64) -> ActivityEntry {
+        ActivityEntry {
+            name,
+            kind: ActivityKind::Boss,
+            rank: 1,
+            score,
+        }
+    }
+
+    #[test]
+    fn kill_counts_seed_unlocks_under_manual_toggles() {
+        let mut state = AppState::default();
+        assert!(state.is_unlocked(Unlock::InfernalCape));
+
+        state.manual_unlocks.insert(Unlock::Rigour, false);
+        state.activities = vec![boss("TzTok-Jad", 3), boss("Chambers of Xeric", 50)];
+        state.seed_unlocks();
+        assert!(state.is_unlocked(Unlock::FireCape));
+        assert!(state.is_unlocked(Unlock::Augury));
+        assert!(!state.is_unlocked(Unlock::InfernalCape));
+        assert!(!state.is_unlocked(Unlock::Rigour));
+    }
 }
@@ -0,0 +1,103 @@
+//! Persistence for data that outlives a session: `localStorage` in the
+//! browser, and JSON files in the user's data directory elsewhere.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Reads a stored value, or `None` when it is missing or unreadable.
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let json = read(key)?;
+    match serde_json::from_str(&json) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            log::warn!("Ignoring unreadable saved data '{key}': {e}");
+            None
+        }
+    }
+}
+
+pub fn save<T: Serialize>(key: &str, value: &T) {
+    match serde_json::to_string(value) {
+        Ok(json) => write(key, &json),
+        Err(e) => log::warn!("Failed to serialise '{key}': {e}"),
+    }
+}
+
+/// Current time as seconds since the Unix epoch.
+#[cfg(target_arch = "wasm32")]
+pub fn now_secs() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+/// Formats a Unix timestamp as a UTC `YYYY-MM-DD` date.
+pub fn format_date(secs: u64) -> String {
+    // Civil-from-days conversion for the proleptic Gregorian calendar
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read(key: &str) -> Option<String> {
+    local_storage()?.get_item(key).ok().flatten()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write(key: &str, json: &str) {
+    let saved = local_storage().is_some_and(|storage| storage.set_item(key, json).is_ok());
+    if !saved {
+        log::warn!("Failed to save '{key}' to local storage");
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn data_dir() -> std::path::PathBuf {
+    use std::path::PathBuf;
+
+    let base = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .unwrap_or_default();
+    base.join("os-rs-gui")
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read(key: &str) -> Option<String> {
+    std::fs::read_to_string(data_dir().join(format!("{key}.json"))).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write(key: &str, json: &str) {
+    let dir = data_dir();
+    let result = std::fs::create_dir_all(&dir)
+        .and_then(|_| std::fs::write(dir.join(format!("{key}.json")), json));
+    if let Err(e) = result {
+        log::warn!("Failed to save '{key}' to {}: {e}", dir.display());
+    }
+}
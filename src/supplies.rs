@@ -0,0 +1,291 @@
+//! Supplies used per kill and how many kills an inventory lasts.
+//!
+//! Damage taken and prayer drained are spread evenly over the kill, each
+//! active boost potion is re-dosed on the boost decay interval, and a trip
+//! ends when the first supply runs out or the banking interval is up.
+//! Supplies are only used while fighting, not while waiting for a respawn.
+
+use crate::boosts::TICK_SECONDS;
+use crate::defence::Protection;
+use osrs::types::player::Player;
+use osrs::types::prayers::Prayer;
+
+/// Inventory slots available for supplies.
+pub const INVENTORY_SLOTS: u32 = 28;
+/// Doses in a full potion.
+const POTION_DOSES: f64 = 4.0;
+
+/// Prayer drain effect of each prayer, from the wiki.
+const PRAYER_DRAIN: [(Prayer, u32); 21] = [
+    (Prayer::ThickSkin, 1),
+    (Prayer::BurstOfStrength, 1),
+    (Prayer::ClarityOfThought, 1),
+    (Prayer::SharpEye, 1),
+    (Prayer::MysticWill, 1),
+    (Prayer::RockSkin, 6),
+    (Prayer::SuperhumanStrength, 6),
+    (Prayer::ImprovedReflexes, 6),
+    (Prayer::HawkEye, 6),
+    (Prayer::MysticLore, 6),
+    (Prayer::SteelSkin, 12),
+    (Prayer::UltimateStrength, 12),
+    (Prayer::IncredibleReflexes, 12),
+    (Prayer::EagleEye, 12),
+    (Prayer::MysticMight, 12),
+    (Prayer::Deadeye, 12),
+    (Prayer::MysticVigour, 12),
+    (Prayer::Chivalry, 24),
+    (Prayer::Piety, 24),
+    (Prayer::Rigour, 24),
+    (Prayer::Augury, 24),
+];
+/// Drain effect of each protection prayer.
+const PROTECTION_DRAIN: u32 = 12;
+
+/// What the inventory is filled with for a trip.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TripSetup {
+    /// Hitpoints healed by each piece of food.
+    pub food_heal: u32,
+    pub food_slots: u32,
+    /// Slots of four-dose prayer potions.
+    pub prayer_slots: u32,
+    /// Slots of four-dose boost potions, shared between the active potions.
+    pub boost_slots: u32,
+    /// Seconds spent banking and walking back between trips.
+    pub bank_secs: u32,
+    /// Minutes between bank trips whether or not supplies are left, or 0 to
+    /// bank only when they run out.
+    pub bank_every_mins: u32,
+    /// Ticks the monster takes to respawn.
+    pub respawn_ticks: u32,
+    /// Ticks lost between kills, e.g. to looting and finding the next target.
+    pub idle_ticks: u32,
+}
+
+impl Default for TripSetup {
+    fn default() -> Self {
+        Self {
+            food_heal: 20,
+            food_slots: 18,
+            prayer_slots: 6,
+            boost_slots: 2,
+            bank_secs: 120,
+            bank_every_mins: 0,
+            respawn_ticks: 0,
+            idle_ticks: 2,
+        }
+    }
+}
+
+impl TripSetup {
+    pub fn used_slots(&self) -> u32 {
+        self.food_slots + self.prayer_slots + self.boost_slots
+    }
+
+    /// Seconds between the end of one kill and the start of the next.
+    pub fn downtime_secs(&self) -> f64 {
+        (self.respawn_ticks + self.idle_ticks) as f64 * TICK_SECONDS
+    }
+}
+
+/// Supplies consumed per kill.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SupplyUse {
+    pub food: f64,
+    pub prayer_doses: f64,
+    pub boost_doses: f64,
+}
+
+/// What ends a trip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Supply {
+    Food,
+    Prayer,
+    Boosts,
+    /// The banking interval is up.
+    Interval,
+}
+
+impl Supply {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Supply::Food => "food",
+            Supply::Prayer => "prayer potions",
+            Supply::Boosts => "boost potions",
+            Supply::Interval => "banking interval",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TripEstimate {
+    pub per_kill: SupplyUse,
+    /// Kills before a supply runs out, or `None` when nothing is used up.
+    pub kills_per_trip: Option<f64>,
+    pub limited_by: Option<Supply>,
+    /// Length of a trip in seconds, excluding banking.
+    pub trip_secs: Option<f64>,
+    /// Seconds from the start of one kill to the start of the next.
+    pub kill_cycle_secs: f64,
+    pub kills_per_hour: f64,
+}
+
+/// Prayer points drained per tick by the active prayers and the chosen
+/// protection prayer.
+pub fn prayer_drain_per_tick(player: &Player, protection: Option<Protection>) -> f64 {
+    let mut drain: u32 = PRAYER_DRAIN
+        .iter()
+        .filter(|(prayer, _)| player.prayers.contains_prayer(*prayer))
+        .map(|(_, drain)| drain)
+        .sum();
+    if protection.is_some() {
+        drain += PROTECTION_DRAIN;
+    }
+    let resistance = 60 + 2 * player.bonuses.prayer.max(0) as u32;
+    drain as f64 / resistance as f64
+}
+
+/// Prayer points restored by a dose of prayer potion.
+pub fn prayer_dose_restore(prayer_level: u32) -> u32 {
+    prayer_level / 4 + 7
+}
+
+/// Supplies used and kills per trip and per hour, for kills that take
+/// `kill_secs` of fighting each while taking `damage_per_tick`.
+///
+/// `boost_potions` potions are re-dosed every `redose_secs`, if set. The
+/// player starts each trip with their current prayer points on top of the
+/// potions.
+pub fn estimate_trip(
+    player: &Player,
+    protection: Option<Protection>,
+    damage_per_tick: f64,
+    kill_secs: f64,
+    boost_potions: usize,
+    redose_secs: Option<f64>,
+    setup: &TripSetup,
+) -> TripEstimate {
+    let kill_ticks = kill_secs / TICK_SECONDS;
+    let prayer_restore = prayer_dose_restore(player.stats.prayer.base) as f64;
+    let per_kill = SupplyUse {
+        food: damage_per_tick * kill_ticks / setup.food_heal.max(1) as f64,
+        prayer_doses: prayer_drain_per_tick(player, protection) * kill_ticks / prayer_restore,
+        boost_doses: redose_secs
+            .filter(|&secs| secs > 0.0)
+            .map_or(0.0, |secs| boost_potions as f64 * kill_secs / secs),
+    };
+
+    // Prayer points at the start of the trip, as doses
+    let starting_doses = player.stats.prayer.current as f64 / prayer_restore;
+    let kill_cycle_secs = kill_secs + setup.downtime_secs();
+    let interval_kills = (setup.bank_every_mins > 0 && kill_cycle_secs > 0.0)
+        .then(|| setup.bank_every_mins as f64 * 60.0 / kill_cycle_secs);
+    let limits = [
+        (Supply::Food, setup.food_slots as f64, per_kill.food),
+        (
+            Supply::Prayer,
+            setup.prayer_slots as f64 * POTION_DOSES + starting_doses,
+            per_kill.prayer_doses,
+        ),
+        (
+            Supply::Boosts,
+            setup.boost_slots as f64 * POTION_DOSES,
+            per_kill.boost_doses,
+        ),
+    ];
+    let limit = limits
+        .into_iter()
+        .filter(|(_, _, used)| *used > 0.0)
+        .map(|(supply, carried, used)| (supply, carried / used))
+        .chain(interval_kills.map(|kills| (Supply::Interval, kills)))
+        .min_by(|a, b| a.1.total_cmp(&b.1));
+
+    let kills_per_trip = limit.map(|(_, kills)| kills.floor());
+    let trip_secs = kills_per_trip.map(|kills| kills * kill_cycle_secs);
+    let cycle_secs = match trip_secs {
+        Some(secs) => secs + setup.bank_secs as f64,
+        None => kill_cycle_secs,
+    };
+    let kills_per_hour = match kills_per_trip {
+        _ if cycle_secs <= 0.0 => 0.0,
+        Some(kills) => kills * 3600.0 / cycle_secs,
+        None => 3600.0 / cycle_secs,
+    };
+
+    TripEstimate {
+        per_kill,
+        kills_per_trip,
+        limited_by: limit.map(|(supply, _)| supply),
+        trip_secs,
+        kill_cycle_secs,
+        kills_per_hour,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skill::{set_skill_base_level, set_skill_current_level, Skill};
+
+    fn player(prayer: u32) -> Player {
+        let mut player = Player::default();
+        set_skill_base_level(&mut player, Skill::Prayer, prayer);
+        set_skill_current_level(&mut player, Skill::Prayer, prayer);
+        player
+    }
+
+    #[test]
+    fn prayer_potions_restore() {
+        assert_eq!(prayer_dose_restore(99), 31);
+        assert_eq!(prayer_dose_restore(70), 24);
+        assert_eq!(prayer_dose_restore(43), 17);
+    }
+
+    #[test]
+    fn protection_prayers_drain() {
+        // A protection prayer with no prayer bonus drains a point every 3 seconds
+        let drain = prayer_drain_per_tick(&player(99), Some(Protection::Magic));
+        assert!((drain - 0.2).abs() < 1e-9);
+        assert_eq!(prayer_drain_per_tick(&player(99), None), 0.0);
+    }
+
+    #[test]
+    fn trip_ends_when_food_runs_out() {
+        // 100-tick kills taking 50 damage each, under Protect from Magic
+        let setup = TripSetup::default();
+        let trip = estimate_trip(
+            &player(99),
+            Some(Protection::Magic),
+            0.5,
+            60.0,
+            1,
+            Some(300.0),
+            &setup,
+        );
+        assert!((trip.per_kill.food - 2.5).abs() < 1e-9);
+        assert!((trip.per_kill.prayer_doses - 20.0 / 31.0).abs() < 1e-9);
+        assert!((trip.per_kill.boost_doses - 0.2).abs() < 1e-9);
+        // 18 food lasts 7.2 kills, ahead of 42 for prayer and 40 for boosts
+        assert_eq!(trip.limited_by, Some(Supply::Food));
+        assert_eq!(trip.kills_per_trip, Some(7.0));
+        assert!((trip.kill_cycle_secs - 61.2).abs() < 1e-9);
+        assert!((trip.kills_per_hour - 7.0 * 3600.0 / (7.0 * 61.2 + 120.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn trip_ends_on_the_banking_interval() {
+        let setup = TripSetup {
+            bank_every_mins: 5,
+            ..TripSetup::default()
+        };
+        let trip = estimate_trip(&player(99), None, 0.0, 60.0, 0, None, &setup);
+        assert_eq!(trip.limited_by, Some(Supply::Interval));
+        assert_eq!(trip.kills_per_trip, Some(4.0));
+
+        // With nothing used up and no interval, kills never stop for the bank
+        let endless = estimate_trip(&player(99), None, 0.0, 60.0, 0, None, &TripSetup::default());
+        assert_eq!(endless.kills_per_trip, None);
+        assert!((endless.kills_per_hour - 3600.0 / 61.2).abs() < 1e-9);
+    }
+}
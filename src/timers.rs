@@ -0,0 +1,26 @@
+//! Async timers for every build target. gloo-timers only works in the
+//! browser, so native builds sleep on async-std instead.
+
+use std::time::Duration;
+
+/// Waits for `duration` without blocking the UI.
+#[cfg(target_arch = "wasm32")]
+pub async fn sleep(duration: Duration) {
+    gloo_timers::future::TimeoutFuture::new(duration.as_millis() as u32).await;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn sleep(duration: Duration) {
+    async_std::task::sleep(duration).await;
+}
+
+/// Lets the UI update before carrying on with a long computation.
+#[cfg(target_arch = "wasm32")]
+pub async fn yield_now() {
+    gloo_timers::future::TimeoutFuture::new(0).await;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn yield_now() {
+    async_std::task::yield_now().await;
+}
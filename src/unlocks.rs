@@ -0,0 +1,106 @@
+use crate::hiscores::ActivityEntry;
+use osrs::types::prayers::Prayer;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+/// Prayers and items that need a boss or raid completion to use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, EnumIter, Serialize, Deserialize)]
+pub enum Unlock {
+    Rigour,
+    Augury,
+    FireCape,
+    InfernalCape,
+    DizanasQuiver,
+}
+
+impl Unlock {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Unlock::Rigour => "Rigour",
+            Unlock::Augury => "Augury",
+            Unlock::FireCape => "Fire cape",
+            Unlock::InfernalCape => "Infernal cape",
+            Unlock::DizanasQuiver => "Dizana's quiver",
+        }
+    }
+
+    /// Hiscore rows where a kill count points to the unlock.
+    pub fn activities(&self) -> &'static [&'static str] {
+        match self {
+            // The prayer scrolls are Chambers of Xeric drops, so completions
+            // only mean the player could have one
+            Unlock::Rigour | Unlock::Augury => {
+                &["Chambers of Xeric", "Chambers of Xeric: Challenge Mode"]
+            }
+            Unlock::FireCape => &["TzTok-Jad"],
+            Unlock::InfernalCape => &["TzKal-Zuk"],
+            Unlock::DizanasQuiver => &["Sol Heredit"],
+        }
+    }
+
+    pub fn prayer(&self) -> Option<Prayer> {
+        match self {
+            Unlock::Rigour => Some(Prayer::Rigour),
+            Unlock::Augury => Some(Prayer::Augury),
+            _ => None,
+        }
+    }
+
+    /// Whether an equipped item with this name needs the unlock.
+    pub fn covers_item(&self, item_name: &str) -> bool {
+        let item_name = item_name.to_lowercase();
+        match self {
+            Unlock::FireCape => item_name.starts_with("fire cape"),
+            Unlock::InfernalCape => item_name.starts_with("infernal cape"),
+            Unlock::DizanasQuiver => item_name.contains("dizana's quiver"),
+            _ => false,
+        }
+    }
+}
+
+/// Unlocks the kill counts on the hiscores point to, used to pre-fill the
+/// toggles. Kill counts below the hiscore threshold aren't listed, and a
+/// Chambers of Xeric completion doesn't mean a prayer scroll dropped, so this
+/// can both miss and overstate an unlock.
+pub fn inferred_unlocks(activities: &[ActivityEntry]) -> HashSet<Unlock> {
+    Unlock::iter()
+        .filter(|unlock| {
+            activities
+                .iter()
+                .any(|entry| entry.score > 0 && unlock.activities().contains(&entry.name))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hiscores::ActivityKind;
+
+    fn boss(name: &'static str, score: u64) -> ActivityEntry {
+        ActivityEntry {
+            name,
+            kind: ActivityKind::Boss,
+            rank: 1,
+            score,
+        }
+    }
+
+    #[test]
+    fn kill_counts_point_to_unlocks() {
+        let inferred = inferred_unlocks(&[boss("TzTok-Jad", 3), boss("Chambers of Xeric", 50)]);
+        assert!(inferred.contains(&Unlock::FireCape));
+        assert!(inferred.contains(&Unlock::Rigour));
+        assert!(inferred.contains(&Unlock::Augury));
+        assert!(!inferred.contains(&Unlock::InfernalCape));
+    }
+
+    #[test]
+    fn items_need_their_unlock() {
+        assert!(Unlock::InfernalCape.covers_item("Infernal cape (l)"));
+        assert!(Unlock::DizanasQuiver.covers_item("Blessed dizana's quiver"));
+        assert!(!Unlock::FireCape.covers_item("Infernal cape"));
+    }
+}